@@ -0,0 +1,51 @@
+//! Times the simulation with swarms of asteroids to show how collision
+//! checking scales.  Run with a list of asteroid counts, e.g.
+//!
+//!     cargo run --release --example swarm -- 10 100 1000 4000
+
+use std::time::Instant;
+
+use quicksilver::geom::Vector;
+
+use asteroids_wasm::config::game_config::{FireMode, GameConfig};
+use asteroids_wasm::sim::inputs::Inputs;
+use asteroids_wasm::sim::simulation::Simulation;
+
+// Long enough for the first wave to arrive, which is then timed
+const WARMUP_TICKS: usize = 100;
+const TIMED_TICKS: usize = 300;
+
+fn main() {
+    let counts: Vec<usize> = std::env::args().skip(1).filter_map(|arg| arg.parse().ok()).collect();
+    let counts = if counts.is_empty() { vec![10, 100, 1000, 4000] } else { counts };
+
+    println!("{:>10} {:>12} {:>12} {:>10} {:>10}", "asteroids", "ms/tick", "ticks/sec", "left", "pool");
+
+    for count in counts {
+        let mut config = GameConfig::default();
+        config.asteroids.swarm = count;
+        // Room for every asteroid to split
+        config.asteroids.pool_size = count * 4;
+        config.player.fire_mode = FireMode::Auto;
+
+        let mut sim = Simulation::new(&Vector::new(1024.0, 768.0), &config, 1);
+
+        // Spin and keep firing so there are always bullets to check
+        let inputs = Inputs { rotate_right: true, fire_held: true, ..Inputs::new() };
+
+        for _ in 0..WARMUP_TICKS {
+            sim.step(&inputs);
+        }
+
+        let start = Instant::now();
+
+        for _ in 0..TIMED_TICKS {
+            sim.step(&inputs);
+        }
+
+        let elapsed = start.elapsed().as_secs_f64() * 1000.0 / TIMED_TICKS as f64;
+        let left = sim.asteroids.alive_count();
+
+        println!("{:>10} {:>12.3} {:>12.0} {:>10} {:>10}", count, elapsed, 1000.0 / elapsed, left, sim.asteroids.capacity());
+    }
+}
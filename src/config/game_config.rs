@@ -0,0 +1,368 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+// Built into the binary so the game always has something sensible to fall back on
+const DEFAULT_CONFIG: &str = include_str!("default_config.toml");
+
+#[cfg(not(target_arch = "wasm32"))]
+const CONFIG_FILE: &str = "config.toml";
+
+/// How the fire button shoots.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FireMode {
+    /// One shot each time fire is pressed
+    Tap,
+    /// Keep shooting while fire is held
+    Auto,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PlayerConfig {
+    pub bullets: usize,
+    pub thrust: f32,
+    pub speed_limit: f32,
+    pub rotate_speed: f32,
+    pub bullet_speed: f32,
+    pub bullet_lifetime: i32,
+    pub fire_mode: FireMode,
+    pub fire_rate: i32,
+    pub max_bullets: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AsteroidConfig {
+    pub pool_size: usize,
+    pub split_count: usize,
+    pub hit_radius: f32,
+    pub swarm: usize,
+    pub bounce: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScoringConfig {
+    pub large: i64,
+    pub medium: i64,
+    pub small: i64,
+}
+
+/// Gameplay tuning values.
+///
+/// The embedded defaults are overlaid with `config.toml` on native builds or
+/// the page's query string on the web, so an override only needs to mention
+/// the settings it changes.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct GameConfig {
+    pub player: PlayerConfig,
+    pub asteroids: AsteroidConfig,
+    pub scoring: ScoringConfig,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The text couldn't be read as TOML or didn't match the expected layout
+    Parse { source: String, error: toml::de::Error },
+    /// A query string setting wasn't of the form `section.key=value`
+    Setting(String),
+    /// A value is outside of what the game can cope with
+    Invalid { setting: &'static str, reason: &'static str },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::Parse { source, error } => write!(f, "{}: {}", source, error),
+            ConfigError::Setting(setting) => write!(f, "'{}' is not a valid setting, expected section.key=value", setting),
+            ConfigError::Invalid { setting, reason } => write!(f, "{} {}", setting, reason),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError { }
+
+impl Default for GameConfig {
+    fn default() -> Self {
+        GameConfig::parse(DEFAULT_CONFIG).expect("The built-in config is invalid")
+    }
+}
+
+impl GameConfig {
+    /// Load the config for this platform.  Problems are reported and the
+    /// built-in defaults used instead, since a typo shouldn't stop the game.
+    pub fn load() -> Self {
+        match GameConfig::load_overrides() {
+            Ok(Some(config)) => config,
+            Ok(None) => GameConfig::default(),
+            Err(e) => {
+                GameConfig::report(&e);
+                GameConfig::default()
+            }
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn report(error: &ConfigError) {
+        eprintln!("Config error, using defaults: {}", error);
+    }
+
+    // Query string overrides are only ever seen in the browser, so say so there
+    #[cfg(target_arch = "wasm32")]
+    fn report(error: &ConfigError) {
+        stdweb::console!(error, format!("Config error, using defaults: {}", error));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_overrides() -> Result<Option<Self>, ConfigError> {
+        match std::fs::read_to_string(CONFIG_FILE) {
+            Ok(text) => GameConfig::parse_overrides(CONFIG_FILE, &text).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_overrides() -> Result<Option<Self>, ConfigError> {
+        let query = stdweb::web::window().location()
+                                         .and_then(|location| location.search().ok())
+                                         .unwrap_or_default();
+
+        if query.trim_start_matches('?').is_empty() {
+            return Ok(None);
+        }
+
+        GameConfig::from_query(&query).map(Some)
+    }
+
+    /// Parse a complete config.
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let value = GameConfig::parse_value("built-in config", text)?;
+        GameConfig::from_value("built-in config", value)
+    }
+
+    /// The complete config as TOML, which `parse` reads back unchanged.
+    pub fn serialize(&self) -> String {
+        toml::to_string(self).expect("Every config can be written as TOML")
+    }
+
+    /// Parse a config that only overrides some of the defaults.
+    pub fn parse_overrides(source: &str, text: &str) -> Result<Self, ConfigError> {
+        let mut value = GameConfig::parse_value("built-in config", DEFAULT_CONFIG)?;
+        merge(&mut value, GameConfig::parse_value(source, text)?);
+
+        GameConfig::from_value(source, value)
+    }
+
+    /// Parse overrides from a query string like `?player.thrust=0.8&player.fire_mode=auto`.
+    /// Parameters that aren't `section.key` settings, like `utm_source`, are
+    /// left for whoever else reads the query string.
+    pub fn from_query(query: &str) -> Result<Self, ConfigError> {
+        let mut text = String::new();
+
+        for setting in query.trim_start_matches('?').split('&').filter(|s| !s.is_empty()) {
+            let setting = percent_decode(setting);
+            let mut parts = setting.splitn(2, '=');
+
+            match (parts.next(), parts.next()) {
+                (Some(key), _) if !key.contains('.') => continue,
+                (Some(key), Some(value)) if !value.is_empty() => {
+                    // Bare words are strings, so `fire_mode=auto` needs no quotes
+                    let value = if is_word(value) { format!("\"{}\"", value) } else { value.to_string() };
+                    text.push_str(&format!("{} = {}\n", key, value));
+                },
+                _ => return Err(ConfigError::Setting(setting)),
+            }
+        }
+
+        GameConfig::parse_overrides("query string", &text)
+    }
+
+    fn parse_value(source: &str, text: &str) -> Result<Value, ConfigError> {
+        text.parse::<Value>().map_err(|error| ConfigError::Parse { source: source.to_string(), error })
+    }
+
+    fn from_value(source: &str, value: Value) -> Result<Self, ConfigError> {
+        let config: GameConfig = value.try_into()
+                                      .map_err(|error| ConfigError::Parse { source: source.to_string(), error })?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let invalid = |setting, reason| Err(ConfigError::Invalid { setting, reason });
+
+        if self.player.bullets == 0 {
+            return invalid("player.bullets", "must be at least 1");
+        }
+        if !positive(self.player.thrust) {
+            return invalid("player.thrust", "must be greater than 0");
+        }
+        if !positive(self.player.speed_limit) {
+            return invalid("player.speed_limit", "must be greater than 0");
+        }
+        if !positive(self.player.rotate_speed) || self.player.rotate_speed > 180.0 {
+            return invalid("player.rotate_speed", "must be between 0 and 180 degrees");
+        }
+        if !positive(self.player.bullet_speed) {
+            return invalid("player.bullet_speed", "must be greater than 0");
+        }
+        if self.player.bullet_lifetime < 1 {
+            return invalid("player.bullet_lifetime", "must be at least 1 tick");
+        }
+        if self.player.fire_rate < 1 {
+            return invalid("player.fire_rate", "must be at least 1 tick");
+        }
+        if self.player.max_bullets == 0 || self.player.max_bullets > self.player.bullets {
+            return invalid("player.max_bullets", "must be between 1 and player.bullets");
+        }
+        if self.asteroids.split_count == 0 {
+            return invalid("asteroids.split_count", "must be at least 1");
+        }
+        if !positive(self.asteroids.hit_radius) {
+            return invalid("asteroids.hit_radius", "must be greater than 0");
+        }
+        if self.scoring.large < 0 || self.scoring.medium < 0 || self.scoring.small < 0 {
+            return invalid("scoring", "values can't be negative");
+        }
+
+        Ok(())
+    }
+}
+
+// A plain `<= 0.0` check would let NaN through, since it fails every comparison
+fn positive(value: f32) -> bool {
+    value.is_finite() && value > 0.0
+}
+
+// Undo a browser's URL encoding, e.g. `%22` for quotes and `+` for spaces.
+// Anything that doesn't decode is left as it is.
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = tail.get(0..2)
+                          .and_then(|hex| std::str::from_utf8(hex).ok())
+                          .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+
+        rest = match (byte, escaped) {
+            (b'%', Some(escaped)) => { bytes.push(escaped); &tail[2..] },
+            (b'+', _) => { bytes.push(b' '); tail },
+            _ => { bytes.push(byte); tail },
+        };
+    }
+
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn is_word(value: &str) -> bool {
+    value.chars().all(|c| c.is_ascii_alphabetic()) && value != "true" && value != "false"
+}
+
+// Overlay `overrides` onto `base`, table by table
+fn merge(base: &mut Value, overrides: Value) {
+    match (base, overrides) {
+        (Value::Table(base), Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => { base.insert(key, value); },
+                }
+            }
+        },
+        (base, overrides) => *base = overrides,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn invalid_setting(result: Result<GameConfig, ConfigError>) -> &'static str {
+        match result {
+            Err(ConfigError::Invalid { setting, .. }) => setting,
+            other => panic!("expected an invalid setting, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_the_built_in_config() {
+        let config = GameConfig::default();
+
+        assert_eq!(config.player.fire_mode, FireMode::Tap);
+        assert_eq!(config.asteroids.pool_size, 27);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn overrides_keep_the_other_defaults() {
+        let config = GameConfig::parse_overrides("test", "[player]\nthrust = 0.8\n\n[scoring]\nsmall = 500\n").unwrap();
+        let defaults = GameConfig::default();
+
+        assert_eq!(config.player.thrust, 0.8);
+        assert_eq!(config.scoring.small, 500);
+        assert_eq!(config.player.speed_limit, defaults.player.speed_limit);
+        assert_eq!(config.asteroids, defaults.asteroids);
+    }
+
+    #[test]
+    fn merges_tables_key_by_key() {
+        let mut base: Value = "[a]\nx = 1\ny = 2\n".parse().unwrap();
+        merge(&mut base, "b = 3\n[a]\ny = 4\n".parse().unwrap());
+
+        assert_eq!(base, "b = 3\n[a]\nx = 1\ny = 4\n".parse::<Value>().unwrap());
+    }
+
+    #[test]
+    fn rejects_unknown_settings_and_bad_toml() {
+        assert!(matches!(GameConfig::parse_overrides("test", "[player]\nthrusst = 0.8"), Err(ConfigError::Parse { .. })));
+        assert!(matches!(GameConfig::parse_overrides("test", "[player"), Err(ConfigError::Parse { .. })));
+        assert!(matches!(GameConfig::parse_overrides("test", "[player]\nthrust = \"fast\""), Err(ConfigError::Parse { .. })));
+    }
+
+    #[test]
+    fn rejects_values_out_of_range() {
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[player]\nthrust = 0.0")), "player.thrust");
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[player]\nthrust = nan")), "player.thrust");
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[player]\nrotate_speed = nan")), "player.rotate_speed");
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[player]\nmax_bullets = 50")), "player.max_bullets");
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[asteroids]\nhit_radius = -inf")), "asteroids.hit_radius");
+        assert_eq!(invalid_setting(GameConfig::parse_overrides("test", "[scoring]\nlarge = -1")), "scoring");
+    }
+
+    #[test]
+    fn reads_settings_from_the_query_string() {
+        let config = GameConfig::from_query("?player.thrust=0.8&player.fire_mode=auto&asteroids.bounce=true").unwrap();
+
+        assert_eq!(config.player.thrust, 0.8);
+        assert_eq!(config.player.fire_mode, FireMode::Auto);
+        assert!(config.asteroids.bounce);
+    }
+
+    #[test]
+    fn decodes_query_string_values() {
+        let config = GameConfig::from_query("player.fire_mode=%22auto%22&scoring.small=%35%30%30").unwrap();
+
+        assert_eq!(config.player.fire_mode, FireMode::Auto);
+        assert_eq!(config.scoring.small, 500);
+        assert_eq!(percent_decode("a+b%2Cc%zz%4"), "a b,c%zz%4");
+    }
+
+    #[test]
+    fn ignores_other_query_parameters() {
+        let config = GameConfig::from_query("?utm_source=news&player.thrust=0.8&debug").unwrap();
+
+        assert_eq!(config.player.thrust, 0.8);
+    }
+
+    #[test]
+    fn rejects_settings_without_values() {
+        assert!(matches!(GameConfig::from_query("player.thrust="), Err(ConfigError::Setting(_))));
+        assert!(matches!(GameConfig::from_query("player.thrust"), Err(ConfigError::Setting(_))));
+    }
+}
@@ -0,0 +1,56 @@
+/// Something the player wants to do, independent of the key that asked for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    RotateLeft,
+    RotateRight,
+    Thrust,
+    Fire,
+    Hyperspace,
+    Pause,
+    Confirm,
+    MenuUp,
+    MenuDown,
+    MenuLeft,
+    MenuRight,
+    Cancel,
+}
+
+impl Action {
+    pub const ALL: [Action; 12] = [
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::Thrust,
+        Action::Fire,
+        Action::Hyperspace,
+        Action::Pause,
+        Action::Confirm,
+        Action::MenuUp,
+        Action::MenuDown,
+        Action::MenuLeft,
+        Action::MenuRight,
+        Action::Cancel,
+    ];
+
+    /// Name shown to the player.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::RotateLeft => "ROTATE LEFT",
+            Action::RotateRight => "ROTATE RIGHT",
+            Action::Thrust => "THRUST",
+            Action::Fire => "FIRE",
+            Action::Hyperspace => "HYPERSPACE",
+            Action::Pause => "PAUSE",
+            Action::Confirm => "CONFIRM",
+            Action::MenuUp => "MENU UP",
+            Action::MenuDown => "MENU DOWN",
+            Action::MenuLeft => "MENU LEFT",
+            Action::MenuRight => "MENU RIGHT",
+            Action::Cancel => "CANCEL",
+        }
+    }
+
+    /// Look an action up by the name it's stored under.
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.iter().copied().find(|a| format!("{:?}", a) == name)
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+use quicksilver::input::Key;
+
+use super::action::Action;
+use crate::persistence::storage::Storage;
+
+pub const MAX_KEYS_PER_ACTION: usize = 3;
+
+const STORAGE_KEY: &str = "bindings";
+
+// Keys that can be saved and bound.  Anything else is ignored.
+const BINDABLE_KEYS: &[Key] = &[
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I,
+    Key::J, Key::K, Key::L, Key::M, Key::N, Key::O, Key::P, Key::Q, Key::R,
+    Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4,
+    Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+    Key::Numpad0, Key::Numpad1, Key::Numpad2, Key::Numpad3, Key::Numpad4,
+    Key::Numpad5, Key::Numpad6, Key::Numpad7, Key::Numpad8, Key::Numpad9,
+    Key::NumpadEnter, Key::Add, Key::Subtract, Key::Multiply, Key::Divide, Key::Decimal,
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6,
+    Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12,
+    Key::Left, Key::Right, Key::Up, Key::Down,
+    Key::Space, Key::Return, Key::Tab, Key::Escape, Key::Back,
+    Key::LShift, Key::RShift, Key::LControl, Key::RControl, Key::LAlt, Key::RAlt,
+    Key::Insert, Key::Delete, Key::Home, Key::End, Key::PageUp, Key::PageDown,
+    Key::Comma, Key::Period, Key::Slash, Key::Semicolon, Key::Apostrophe,
+    Key::LBracket, Key::RBracket, Key::Backslash, Key::Minus, Key::Equals, Key::Grave,
+];
+
+pub fn is_bindable(key: Key) -> bool {
+    BINDABLE_KEYS.contains(&key)
+}
+
+pub fn key_name(key: Key) -> String {
+    format!("{:?}", key)
+}
+
+pub fn parse_key(name: &str) -> Option<Key> {
+    BINDABLE_KEYS.iter().copied().find(|k| key_name(*k) == name)
+}
+
+/// Which keys trigger which actions.  An action can have several keys and
+/// a key can trigger several actions, e.g. `Up` is both thrust and menu up.
+///
+/// Stored as one `Action Key Key...` line per action.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bindings {
+    keys: HashMap<Action, Vec<Key>>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        let defaults: &[(Action, &[Key])] = &[
+            (Action::RotateLeft, &[Key::Left]),
+            (Action::RotateRight, &[Key::Right]),
+            (Action::Thrust, &[Key::Up]),
+            (Action::Fire, &[Key::Space]),
+            (Action::Hyperspace, &[Key::Down, Key::LShift, Key::RShift]),
+            (Action::Pause, &[Key::Escape, Key::P]),
+            (Action::Confirm, &[Key::Return]),
+            (Action::MenuUp, &[Key::Up]),
+            (Action::MenuDown, &[Key::Down]),
+            (Action::MenuLeft, &[Key::Left]),
+            (Action::MenuRight, &[Key::Right]),
+            (Action::Cancel, &[Key::Escape, Key::Back]),
+        ];
+
+        Self {
+            keys: defaults.iter().map(|(action, keys)| (*action, keys.to_vec())).collect(),
+        }
+    }
+}
+
+impl Bindings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(storage: &dyn Storage) -> Self {
+        match storage.load(STORAGE_KEY) {
+            Some(text) => Bindings::parse(&text),
+            None => Bindings::new(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<()> {
+        storage.save(STORAGE_KEY, &self.serialize())
+    }
+
+    /// Read stored bindings on top of the defaults.  Unknown actions and
+    /// keys are skipped, and an action that ends up with no keys keeps its
+    /// default ones so it can never become unusable.
+    pub fn parse(text: &str) -> Self {
+        let mut bindings = Bindings::new();
+
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+
+            if let Some(action) = parts.next().and_then(Action::from_name) {
+                let keys: Vec<Key> = parts.filter_map(parse_key)
+                                          .take(MAX_KEYS_PER_ACTION)
+                                          .collect();
+
+                if !keys.is_empty() {
+                    bindings.keys.insert(action, keys);
+                }
+            }
+        }
+
+        bindings
+    }
+
+    pub fn serialize(&self) -> String {
+        Action::ALL.iter()
+                   .map(|action| {
+                       let keys: Vec<String> = self.keys(*action).iter().map(|k| key_name(*k)).collect();
+                       format!("{:?} {}\n", action, keys.join(" "))
+                   })
+                   .collect()
+    }
+
+    pub fn keys(&self, action: Action) -> &[Key] {
+        self.keys.get(&action).map(|keys| keys.as_slice()).unwrap_or(&[])
+    }
+
+    /// Every action the key is bound to.
+    pub fn actions_for(&self, key: Key) -> Vec<Action> {
+        Action::ALL.iter()
+                   .copied()
+                   .filter(|action| self.keys(*action).contains(&key))
+                   .collect()
+    }
+
+    /// Add a key to an action, dropping its oldest key if it already has
+    /// as many as it can hold.
+    pub fn bind(&mut self, action: Action, key: Key) {
+        if !is_bindable(key) {
+            return;
+        }
+
+        let keys = self.keys.entry(action).or_default();
+
+        if keys.contains(&key) {
+            return;
+        }
+
+        if keys.len() >= MAX_KEYS_PER_ACTION {
+            keys.remove(0);
+        }
+
+        keys.push(key);
+    }
+
+    /// Remove the most recently added key, always leaving at least one.
+    pub fn unbind_last(&mut self, action: Action) {
+        if let Some(keys) = self.keys.get_mut(&action) {
+            if keys.len() > 1 {
+                keys.pop();
+            }
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = Bindings::default();
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+use quicksilver::input::{Event, GamepadAxis, GamepadButton, GamepadId};
+
+use super::action::Action;
+
+/// Stick movement smaller than this is treated as the stick being at rest.
+pub const STICK_DEADZONE: f32 = 0.25;
+
+// How far the stick has to be pushed to count as a menu step
+pub(super) const MENU_THRESHOLD: f32 = 0.6;
+
+/// Gamepad input, separated from quicksilver's events so the mapping can be
+/// driven by hand.  `Id` is whatever identifies a pad, `GamepadId` in the
+/// game and anything convenient, like a number, elsewhere.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEvent<Id> {
+    Connected(Id),
+    Disconnected(Id),
+    Button { pad: Id, button: GamepadButton, is_down: bool },
+    Axis { pad: Id, axis: GamepadAxis, value: f32 },
+}
+
+impl GamepadEvent<GamepadId> {
+    pub fn from_event(event: &Event) -> Option<Self> {
+        match event {
+            Event::GamepadConnected(e) => Some(GamepadEvent::Connected(e.gamepad().clone())),
+            Event::GamepadDisconnected(e) => Some(GamepadEvent::Disconnected(e.gamepad().clone())),
+            Event::GamepadButton(e) if !e.is_repeat() => Some(GamepadEvent::Button {
+                pad: e.gamepad().clone(),
+                button: e.button(),
+                is_down: e.is_down(),
+            }),
+            Event::GamepadAxis(e) => Some(GamepadEvent::Axis {
+                pad: e.gamepad().clone(),
+                axis: e.axis(),
+                value: e.value(),
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct PadState {
+    buttons: HashSet<GamepadButton>,
+    stick_x: f32,
+    stick_y: f32,
+}
+
+/// Turns gamepad events into the same actions the keyboard produces.
+///
+/// The left stick and d-pad rotate, the right trigger or shoulder thrusts,
+/// south and west fire, east and north jump to hyperspace and start pauses.
+/// In menus the d-pad or stick moves, south confirms and east cancels.
+pub struct GamepadMapper<Id> {
+    pads: HashMap<Id, PadState>,
+}
+
+impl<Id: Eq + Hash + Clone> Default for GamepadMapper<Id> {
+    fn default() -> Self {
+        Self { pads: HashMap::new() }
+    }
+}
+
+impl<Id: Eq + Hash + Clone> GamepadMapper<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn connected(&self) -> usize {
+        self.pads.len()
+    }
+
+    /// Apply an event, returning the actions it completed.  These are the
+    /// gamepad equivalent of a key being released.
+    pub fn handle(&mut self, event: &GamepadEvent<Id>) -> Vec<Action> {
+        match event {
+            GamepadEvent::Connected(pad) => {
+                self.pads.entry(pad.clone()).or_default();
+                vec![]
+            },
+            GamepadEvent::Disconnected(pad) => {
+                // Forget everything held so nothing stays stuck on
+                self.pads.remove(pad);
+                vec![]
+            },
+            GamepadEvent::Button { pad, button, is_down } => {
+                // Pads can send input before (or without) a connected event
+                let state = self.pads.entry(pad.clone()).or_default();
+
+                if *is_down {
+                    state.buttons.insert(*button);
+                    vec![]
+                } else {
+                    state.buttons.remove(button);
+                    button_actions(*button).to_vec()
+                }
+            },
+            GamepadEvent::Axis { pad, axis, value } => {
+                let state = self.pads.entry(pad.clone()).or_default();
+
+                match axis {
+                    GamepadAxis::LeftStickX => {
+                        let action = menu_step(state.stick_x, *value, Action::MenuLeft, Action::MenuRight);
+                        state.stick_x = *value;
+                        action.into_iter().collect()
+                    },
+                    // Up is positive on the stick but negative on screen
+                    GamepadAxis::LeftStickY => {
+                        let action = menu_step(state.stick_y, *value, Action::MenuDown, Action::MenuUp);
+                        state.stick_y = *value;
+                        action.into_iter().collect()
+                    },
+                    _ => vec![],
+                }
+            },
+        }
+    }
+
+    /// Actions held down on any connected pad.
+    pub fn held(&self) -> Vec<Action> {
+        let mut actions = vec![];
+
+        for state in self.pads.values() {
+            let pressed = |button| state.buttons.contains(&button);
+
+            if state.stick_x < -STICK_DEADZONE || pressed(GamepadButton::DPadLeft) {
+                actions.push(Action::RotateLeft);
+            }
+
+            if state.stick_x > STICK_DEADZONE || pressed(GamepadButton::DPadRight) {
+                actions.push(Action::RotateRight);
+            }
+
+            if pressed(GamepadButton::RightTrigger) || pressed(GamepadButton::RightShoulder) {
+                actions.push(Action::Thrust);
+            }
+
+            for button in state.buttons.iter() {
+                actions.extend_from_slice(button_actions(*button));
+            }
+        }
+
+        actions.sort_by_key(|a| *a as usize);
+        actions.dedup();
+        actions
+    }
+}
+
+fn button_actions(button: GamepadButton) -> &'static [Action] {
+    match button {
+        GamepadButton::South => &[Action::Fire, Action::Confirm],
+        GamepadButton::West => &[Action::Fire],
+        GamepadButton::East => &[Action::Hyperspace, Action::Cancel],
+        GamepadButton::North => &[Action::Hyperspace],
+        GamepadButton::Start => &[Action::Pause],
+        GamepadButton::Select => &[Action::Cancel],
+        GamepadButton::DPadUp => &[Action::MenuUp],
+        GamepadButton::DPadDown => &[Action::MenuDown],
+        GamepadButton::DPadLeft => &[Action::MenuLeft],
+        GamepadButton::DPadRight => &[Action::MenuRight],
+        _ => &[],
+    }
+}
+
+// A menu step happens once each time the stick is pushed past the threshold
+pub(super) fn menu_step(previous: f32, value: f32, negative: Action, positive: Action) -> Option<Action> {
+    if value <= -MENU_THRESHOLD && previous > -MENU_THRESHOLD {
+        Some(negative)
+    } else if value >= MENU_THRESHOLD && previous < MENU_THRESHOLD {
+        Some(positive)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn axis(axis: GamepadAxis, value: f32) -> GamepadEvent<u32> {
+        GamepadEvent::Axis { pad: 0, axis, value }
+    }
+
+    fn button(button: GamepadButton, is_down: bool) -> GamepadEvent<u32> {
+        GamepadEvent::Button { pad: 0, button, is_down }
+    }
+
+    #[test]
+    fn stick_rotates_outside_the_deadzone() {
+        let mut gamepads = GamepadMapper::new();
+
+        gamepads.handle(&axis(GamepadAxis::LeftStickX, -STICK_DEADZONE));
+        assert_eq!(gamepads.held(), vec![]);
+
+        gamepads.handle(&axis(GamepadAxis::LeftStickX, -0.3));
+        assert_eq!(gamepads.held(), vec![Action::RotateLeft]);
+
+        gamepads.handle(&axis(GamepadAxis::LeftStickX, 0.3));
+        assert_eq!(gamepads.held(), vec![Action::RotateRight]);
+
+        gamepads.handle(&axis(GamepadAxis::LeftStickX, 0.1));
+        assert_eq!(gamepads.held(), vec![]);
+    }
+
+    // Triggers only ever arrive as buttons, the backend deciding how far in
+    // counts as pressed, so there's no threshold of our own to cross
+    #[test]
+    fn trigger_or_shoulder_thrusts() {
+        let mut gamepads = GamepadMapper::new();
+
+        gamepads.handle(&button(GamepadButton::RightTrigger, true));
+        assert_eq!(gamepads.held(), vec![Action::Thrust]);
+
+        gamepads.handle(&button(GamepadButton::RightShoulder, true));
+        gamepads.handle(&button(GamepadButton::RightTrigger, false));
+        assert_eq!(gamepads.held(), vec![Action::Thrust]);
+
+        gamepads.handle(&button(GamepadButton::RightShoulder, false));
+        assert_eq!(gamepads.held(), vec![]);
+    }
+
+    #[test]
+    fn stick_steps_menus_once_per_push() {
+        let mut gamepads = GamepadMapper::new();
+
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickY, MENU_THRESHOLD - 0.01)), vec![]);
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickY, MENU_THRESHOLD)), vec![Action::MenuUp]);
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickY, 1.0)), vec![]);
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickY, 0.0)), vec![]);
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickY, -MENU_THRESHOLD)), vec![Action::MenuDown]);
+        assert_eq!(gamepads.handle(&axis(GamepadAxis::LeftStickX, MENU_THRESHOLD)), vec![Action::MenuRight]);
+    }
+
+    #[test]
+    fn buttons_complete_actions_on_release() {
+        let mut gamepads = GamepadMapper::new();
+
+        assert_eq!(gamepads.handle(&button(GamepadButton::South, true)), vec![]);
+        assert_eq!(gamepads.held(), vec![Action::Fire, Action::Confirm]);
+        assert_eq!(gamepads.handle(&button(GamepadButton::South, false)), vec![Action::Fire, Action::Confirm]);
+        assert_eq!(gamepads.held(), vec![]);
+    }
+
+    #[test]
+    fn disconnecting_releases_everything() {
+        let mut gamepads = GamepadMapper::new();
+
+        gamepads.handle(&GamepadEvent::Connected(0));
+        gamepads.handle(&GamepadEvent::Connected(1));
+        gamepads.handle(&button(GamepadButton::RightTrigger, true));
+        gamepads.handle(&axis(GamepadAxis::LeftStickX, -1.0));
+        assert_eq!(gamepads.held(), vec![Action::RotateLeft, Action::Thrust]);
+
+        assert_eq!(gamepads.handle(&GamepadEvent::Disconnected(0)), vec![]);
+        assert_eq!(gamepads.held(), vec![]);
+        assert_eq!(gamepads.connected(), 1);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod action;
+pub mod bindings;
+pub mod gamepad;
+pub mod touch;
+pub mod touch_source;
@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use quicksilver::{
+    geom::{Circle, Rectangle, Vector},
+    graphics::{Color, Graphics},
+};
+
+use super::action::Action;
+use super::gamepad::{menu_step, STICK_DEADZONE};
+use crate::util::math::VectorMath;
+use crate::v;
+
+// How far past its ring a touch can start and still grab a control
+const GRAB_MARGIN: f32 = 1.3;
+
+const STICK_RADIUS: f32 = 80.0;
+const KNOB_RADIUS: f32 = 32.0;
+
+/// Touch input in playfield coordinates.  `Id` tells simultaneous touches
+/// apart, so one finger can steer while others fire and thrust.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TouchEvent<Id> {
+    Down { touch: Id, location: Vector },
+    Moved { touch: Id, location: Vector },
+    Up { touch: Id },
+    /// The touch was taken away, e.g. by the browser, and shouldn't count
+    Cancelled { touch: Id },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Stick,
+    Fire,
+    Thrust,
+    Hyperspace,
+    Pause,
+}
+
+const BUTTONS: [Control; 4] = [Control::Fire, Control::Thrust, Control::Hyperspace, Control::Pause];
+
+/// An on-screen joystick and buttons drawn over the game.
+///
+/// Hidden until the first touch, after which the stick on the left rotates
+/// and the buttons on the right fire, thrust, jump to hyperspace and pause.
+/// In menus the stick moves, fire confirms and pause goes back.
+pub struct TouchControls<Id> {
+    visible: bool,
+    stick_center: Vector,
+    fire: Circle,
+    thrust: Circle,
+    hyperspace: Circle,
+    pause: Circle,
+    // The stick's offset from its center, from -1 to 1 on each axis
+    stick: Vector,
+    touches: HashMap<Id, Control>,
+}
+
+impl<Id: Eq + Hash + Clone> TouchControls<Id> {
+    pub fn new(playfield: Vector) -> Self {
+        let (w, h) = (playfield.x, playfield.y);
+
+        Self {
+            visible: false,
+            stick_center: v!(150.0, h - 150.0),
+            fire: Circle::new(v!(w - 120.0, h - 150.0), 56.0),
+            thrust: Circle::new(v!(w - 250.0, h - 100.0), 48.0),
+            hyperspace: Circle::new(v!(w - 130.0, h - 290.0), 36.0),
+            pause: Circle::new(v!(w - 40.0, 90.0), 22.0),
+            stick: v!(0.0, 0.0),
+            touches: HashMap::new(),
+        }
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    fn button(&self, control: Control) -> &Circle {
+        match control {
+            Control::Fire => &self.fire,
+            Control::Thrust => &self.thrust,
+            Control::Hyperspace => &self.hyperspace,
+            Control::Pause => &self.pause,
+            Control::Stick => unreachable!("The stick isn't a button"),
+        }
+    }
+
+    fn pressed(&self, control: Control) -> bool {
+        self.touches.values().any(|c| *c == control)
+    }
+
+    fn control_at(&self, location: Vector) -> Option<Control> {
+        if !self.pressed(Control::Stick) && location.distance(self.stick_center) <= STICK_RADIUS * GRAB_MARGIN {
+            return Some(Control::Stick);
+        }
+
+        BUTTONS.iter()
+               .copied()
+               .find(|control| {
+                   let button = self.button(*control);
+                   location.distance(button.pos) <= button.radius * GRAB_MARGIN
+               })
+    }
+
+    // Move the stick, returning any menu step it made
+    fn move_stick(&mut self, location: Vector) -> Vec<Action> {
+        let stick = (location - self.stick_center).limit(STICK_RADIUS).divide(STICK_RADIUS);
+
+        // Down is positive on screen
+        let steps = vec![
+            menu_step(self.stick.x, stick.x, Action::MenuLeft, Action::MenuRight),
+            menu_step(self.stick.y, stick.y, Action::MenuUp, Action::MenuDown),
+        ];
+
+        self.stick = stick;
+        steps.into_iter().flatten().collect()
+    }
+
+    /// Apply an event, returning the actions it completed.  These are the
+    /// touch equivalent of a key being released.
+    pub fn handle(&mut self, event: &TouchEvent<Id>) -> Vec<Action> {
+        match event {
+            TouchEvent::Down { touch, location } => {
+                self.visible = true;
+
+                match self.control_at(*location) {
+                    Some(control) => {
+                        self.touches.insert(touch.clone(), control);
+
+                        if control == Control::Stick {
+                            self.move_stick(*location)
+                        } else {
+                            vec![]
+                        }
+                    },
+                    None => vec![],
+                }
+            },
+            TouchEvent::Moved { touch, location } => {
+                match self.touches.get(touch) {
+                    Some(Control::Stick) => self.move_stick(*location),
+                    _ => vec![],
+                }
+            },
+            TouchEvent::Up { touch } => {
+                match self.touches.remove(touch) {
+                    Some(Control::Stick) => {
+                        self.stick = v!(0.0, 0.0);
+                        vec![]
+                    },
+                    Some(control) => control_actions(control).to_vec(),
+                    None => vec![],
+                }
+            },
+            TouchEvent::Cancelled { touch } => {
+                if let Some(Control::Stick) = self.touches.remove(touch) {
+                    self.stick = v!(0.0, 0.0);
+                }
+
+                vec![]
+            },
+        }
+    }
+
+    /// Actions held down by any touch.
+    pub fn held(&self) -> Vec<Action> {
+        let mut actions = vec![];
+
+        if self.stick.x < -STICK_DEADZONE {
+            actions.push(Action::RotateLeft);
+        }
+
+        if self.stick.x > STICK_DEADZONE {
+            actions.push(Action::RotateRight);
+        }
+
+        if self.pressed(Control::Thrust) {
+            actions.push(Action::Thrust);
+        }
+
+        for control in self.touches.values() {
+            actions.extend_from_slice(control_actions(*control));
+        }
+
+        actions.sort_by_key(|a| *a as usize);
+        actions.dedup();
+        actions
+    }
+
+    pub fn render(&self, gfx: &mut Graphics) {
+        if !self.visible {
+            return;
+        }
+
+        let outline = Color::from_rgba(255, 255, 255, 0.35);
+        let fill = Color::from_rgba(255, 255, 255, 0.2);
+
+        // Joystick
+        gfx.stroke_circle(&Circle::new(self.stick_center, STICK_RADIUS), outline);
+
+        let knob = Circle::new(self.stick_center + self.stick.multiply(STICK_RADIUS), KNOB_RADIUS);
+        gfx.fill_circle(&knob, fill);
+        gfx.stroke_circle(&knob, outline);
+
+        // Buttons, filled in while held
+        for control in BUTTONS.iter() {
+            let button = self.button(*control);
+
+            if self.pressed(*control) {
+                gfx.fill_circle(button, fill);
+            }
+
+            gfx.stroke_circle(button, outline);
+            render_icon(gfx, *control, button, outline);
+        }
+    }
+}
+
+fn control_actions(control: Control) -> &'static [Action] {
+    match control {
+        Control::Fire => &[Action::Fire, Action::Confirm],
+        Control::Hyperspace => &[Action::Hyperspace],
+        Control::Pause => &[Action::Pause, Action::Cancel],
+        _ => &[],
+    }
+}
+
+fn render_icon(gfx: &mut Graphics, control: Control, button: &Circle, color: Color) {
+    let c = button.pos;
+    let r = button.radius;
+
+    match control {
+        Control::Fire => {
+            gfx.fill_circle(&Circle::new(c, r * 0.2), color);
+        },
+        Control::Thrust => {
+            let ship = [v!(0.0, -0.5), v!(0.35, 0.4), v!(-0.35, 0.4)];
+            let ship: Vec<Vector> = ship.iter().map(|p| c + p.multiply(r)).collect();
+            gfx.stroke_polygon(&ship, color);
+        },
+        Control::Hyperspace => {
+            gfx.stroke_circle(&Circle::new(c, r * 0.5), color);
+            gfx.stroke_circle(&Circle::new(c, r * 0.2), color);
+        },
+        Control::Pause => {
+            let bar = v!(r * 0.2, r * 0.9);
+            gfx.fill_rect(&Rectangle::new(c + v!(-r * 0.35, -r * 0.45), bar), color);
+            gfx.fill_rect(&Rectangle::new(c + v!(r * 0.15, -r * 0.45), bar), color);
+        },
+        Control::Stick => { },
+    }
+}
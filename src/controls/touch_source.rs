@@ -0,0 +1,179 @@
+use quicksilver::input::Event;
+
+use super::touch::TouchEvent;
+use crate::util::camera::Camera;
+
+#[cfg(target_arch = "wasm32")]
+pub use self::browser::{TouchId, TouchSource};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use self::mouse::{TouchId, TouchSource};
+
+/// The browser's touch events.  Pointer events don't say where a touch
+/// started, so these are listened for on the canvas directly.
+#[cfg(target_arch = "wasm32")]
+mod browser {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use quicksilver::geom::Vector;
+    use stdweb::traits::*;
+    use stdweb::unstable::TryInto;
+    use stdweb::web::{document, HtmlElement};
+    use stdweb::web::event::{TouchCancel, TouchEnd, TouchMove, TouchStart};
+
+    use super::*;
+    use crate::v;
+
+    /// The browser's identifier for a touch.
+    pub type TouchId = i32;
+
+    type Queue = Rc<RefCell<Vec<TouchEvent<TouchId>>>>;
+
+    pub struct TouchSource {
+        queue: Queue,
+    }
+
+    impl Default for TouchSource {
+        fn default() -> Self {
+            let queue = Queue::default();
+
+            let canvas: Option<HtmlElement> = document().query_selector("canvas")
+                                                        .ok()
+                                                        .flatten()
+                                                        .and_then(|canvas| canvas.try_into().ok());
+
+            // Without a canvas there are simply no touch controls
+            if let Some(canvas) = canvas {
+                listen(&canvas, &queue);
+            }
+
+            Self { queue }
+        }
+    }
+
+    impl TouchSource {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Touches come from the canvas rather than window events, see `drain`.
+        pub fn from_event(&mut self, _event: &Event, _camera: &Camera) -> Option<TouchEvent<TouchId>> {
+            None
+        }
+
+        /// Touches since the last call, in playfield coordinates.
+        pub fn drain(&mut self, camera: &Camera) -> Vec<TouchEvent<TouchId>> {
+            self.queue.borrow_mut()
+                      .drain(..)
+                      .map(|event| match event {
+                          TouchEvent::Down { touch, location } => TouchEvent::Down { touch, location: camera.to_playfield(location) },
+                          TouchEvent::Moved { touch, location } => TouchEvent::Moved { touch, location: camera.to_playfield(location) },
+                          event => event,
+                      })
+                      .collect()
+        }
+    }
+
+    // Queue touches with their location relative to the canvas.  The default
+    // is prevented so the page doesn't scroll or zoom under the player.
+    fn listen(canvas: &HtmlElement, queue: &Queue) {
+        let location = |canvas: &HtmlElement, x: f64, y: f64| -> Vector {
+            let rect = canvas.get_bounding_client_rect();
+            v!((x - rect.get_left()) as f32, (y - rect.get_top()) as f32)
+        };
+
+        let (element, events) = (canvas.clone(), queue.clone());
+        canvas.add_event_listener(move |e: TouchStart| {
+            e.prevent_default();
+            for touch in e.changed_touches() {
+                let location = location(&element, touch.client_x(), touch.client_y());
+                events.borrow_mut().push(TouchEvent::Down { touch: touch.identifier(), location });
+            }
+        });
+
+        let (element, events) = (canvas.clone(), queue.clone());
+        canvas.add_event_listener(move |e: TouchMove| {
+            e.prevent_default();
+            for touch in e.changed_touches() {
+                let location = location(&element, touch.client_x(), touch.client_y());
+                events.borrow_mut().push(TouchEvent::Moved { touch: touch.identifier(), location });
+            }
+        });
+
+        let events = queue.clone();
+        canvas.add_event_listener(move |e: TouchEnd| {
+            e.prevent_default();
+            for touch in e.changed_touches() {
+                events.borrow_mut().push(TouchEvent::Up { touch: touch.identifier() });
+            }
+        });
+
+        let events = queue.clone();
+        canvas.add_event_listener(move |e: TouchCancel| {
+            for touch in e.changed_touches() {
+                events.borrow_mut().push(TouchEvent::Cancelled { touch: touch.identifier() });
+            }
+        });
+    }
+}
+
+/// The mouse standing in for a single finger, for trying the touch controls
+/// out on the desktop.  Only enabled with `--touch`.
+#[cfg(not(target_arch = "wasm32"))]
+mod mouse {
+    use quicksilver::geom::Vector;
+    use quicksilver::input::{MouseButton, PointerId};
+
+    use super::*;
+    use crate::v;
+
+    pub type TouchId = PointerId;
+
+    pub struct TouchSource {
+        enabled: bool,
+        // Presses don't carry a location, so remember the last one
+        location: Vector,
+    }
+
+    impl Default for TouchSource {
+        fn default() -> Self {
+            Self {
+                enabled: std::env::args().any(|arg| arg == "--touch"),
+                location: v!(0.0, 0.0),
+            }
+        }
+    }
+
+    impl TouchSource {
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// The touch a window event stands for, in playfield coordinates.
+        pub fn from_event(&mut self, event: &Event, camera: &Camera) -> Option<TouchEvent<TouchId>> {
+            if !self.enabled {
+                return None;
+            }
+
+            match event {
+                Event::PointerMoved(e) => {
+                    self.location = camera.to_playfield(e.location());
+                    Some(TouchEvent::Moved { touch: *e.pointer(), location: self.location })
+                },
+                Event::PointerInput(e) if e.button() == MouseButton::Left => {
+                    if e.is_down() {
+                        Some(TouchEvent::Down { touch: *e.pointer(), location: self.location })
+                    } else {
+                        Some(TouchEvent::Up { touch: *e.pointer() })
+                    }
+                },
+                _ => None,
+            }
+        }
+
+        pub fn drain(&mut self, _camera: &Camera) -> Vec<TouchEvent<TouchId>> {
+            vec![]
+        }
+    }
+}
@@ -1,29 +1,68 @@
 mod util;
+pub mod config;
+pub mod controls;
 mod scenes;
+pub mod persistence;
+pub mod sim;
 
 use quicksilver::{
-    input::Event,
-    input::Key,
+    input::{Event, GamepadId},
     geom::Vector,
-    graphics::{VectorFont, FontRenderer},
+    graphics::VectorFont,
     Graphics, Input, Result, Timer, Window,
 };
 
-use scenes::scene::{Scene, Transition};
+use scenes::scene::{Scene, SceneId, Transition};
 use scenes::asteroids_scene::AsteroidsScene;
+use scenes::title_scene::TitleScene;
+use scenes::pause_scene::PauseScene;
+use scenes::high_scores_scene::HighScoresScene;
+use scenes::enter_initials_scene::EnterInitialsScene;
+use scenes::controls_scene::ControlsScene;
+use controls::action::Action;
+use controls::bindings::Bindings;
+use controls::gamepad::{GamepadEvent, GamepadMapper};
+use controls::touch::{TouchControls, TouchEvent};
+use controls::touch_source::{TouchId, TouchSource};
+use config::game_config::GameConfig;
+use persistence::high_scores::HighScoreTable;
+use persistence::storage::platform_storage;
+use sim::replay::Replay;
+use util::camera::{actual_window_size, Camera};
+use util::rng::GameRng;
 
+// Only a handful of scenes are ever alive at once, so the size difference
+// between variants isn't worth boxing over
+#[allow(clippy::large_enum_variant)]
 enum SceneType {
+    Title(TitleScene),
     Asteroids(AsteroidsScene),
+    Pause(PauseScene),
+    HighScores(HighScoresScene),
+    EnterInitials(EnterInitialsScene),
+    Controls(ControlsScene),
+}
+
+impl SceneType {
+    fn scene(&mut self) -> &mut dyn Scene {
+        match self {
+            SceneType::Title(scene) => scene,
+            SceneType::Asteroids(scene) => scene,
+            SceneType::Pause(scene) => scene,
+            SceneType::HighScores(scene) => scene,
+            SceneType::EnterInitials(scene) => scene,
+            SceneType::Controls(scene) => scene,
+        }
+    }
 }
 
 pub async fn app(window: Window, mut gfx: Graphics, mut input: Input) -> Result<()> {
-    // let window_size = window.size();
-    // HACK: Quicksilver has a bug that does not return correct window size
-    // when using WASM deployment.  For now we're hard coding the
-    // window size.
-    let window_size = v!(1024.0, 768.0);
+    // The game is laid out on a fixed logical playfield that the camera
+    // scales to whatever size the window or canvas happens to be
+    let mut camera = Camera::new();
+    camera.resize(&mut gfx, actual_window_size(window.size()));
 
-    println!("Window Size: {:?}", window_size);  // Default: 1024.0 x 768.0
+    let window_size = camera.playfield();
 
     // Load font
     let ttf = VectorFont::load("ShareTechMono-Regular.ttf").await?;
@@ -31,33 +70,48 @@ pub async fn app(window: Window, mut gfx: Graphics, mut input: Input) -> Result<
     let mut update_timer = Timer::time_per_second(30.0);
     let mut draw_timer = Timer::time_per_second(60.0);
 
-    let font48 = ttf.to_renderer(&gfx, 48.0)?;
-    let font16 = ttf.to_renderer(&gfx, 16.0)?;
-    let mut scenes = initialize_game_scenes(&window_size, font48, font16);
+    let config = GameConfig::load();
+    let mut bindings = Bindings::load(&*platform_storage());
+    let mut gamepads = GamepadMapper::new();
+    let mut touch_source = TouchSource::new();
+    let mut touch = TouchControls::new(window_size);
+
+    let mut scenes = initialize_game_scenes(&window_size, &config, &ttf, &gfx)?;
 
-    loop {
+    // An empty scene stack means the player has quit
+    while !scenes.is_empty() {
         let scene = get_current_game_scene(&mut scenes);
 
-        handle_input_events(&mut input, scene).await;
+        let mut touches = handle_input_events(&mut input, scene, &bindings, &mut gamepads, &mut touch_source, &mut camera, &mut gfx).await;
+        touches.extend(touch_source.drain(&camera));
+
+        handle_touch_events(&touches, &mut touch, scene);
 
-        update_game_scene(&mut update_timer, &mut input, scene);
+        update_game_scene(&mut update_timer, &mut input, &bindings, &gamepads, &touch, scene);
 
-        render_game_scene(&mut draw_timer, &window, &mut gfx, scene)?;
+        render_game_scenes(&mut draw_timer, &window, &mut gfx, &camera, &touch, &mut scenes)?;
+
+        let scene = get_current_game_scene(&mut scenes);
 
         if scene.should_transition() {
-            let font48 = ttf.to_renderer(&gfx, 48.0)?;
-            let font16 = ttf.to_renderer(&gfx, 16.0)?;
-            handle_scene_transition(scene.get_transition(), &mut scenes, &window_size, font48, font16);
+            if let Some(transition) = scene.take_transition() {
+                handle_scene_transition(transition, &mut scenes, &window_size, &config, &ttf, &gfx)?;
+
+                // Pick up any changes made on the controls screen
+                bindings = Bindings::load(&*platform_storage());
+            }
         }
     }
+
+    Ok(())
 }
 
-fn get_current_game_scene(scenes: &mut Vec<SceneType>) -> &mut dyn Scene {
-    debug_assert!(scenes.len() > 0);
+fn get_current_game_scene(scenes: &mut [SceneType]) -> &mut dyn Scene {
+    debug_assert!(!scenes.is_empty());
 
     match scenes.last_mut() {
-        Some(SceneType::Asteroids(scene)) => scene,
-        _ => {
+        Some(scene) => scene.scene(),
+        None => {
             // This should not happen.  There should always be at least
             // one state in the stack so the game knows what to render.
             panic!("No states in state stack!");
@@ -65,63 +119,189 @@ fn get_current_game_scene(scenes: &mut Vec<SceneType>) -> &mut dyn Scene {
     }
 }
 
-fn initialize_game_scenes(window_size: &Vector, font48: FontRenderer, font16: FontRenderer) -> Vec<SceneType> {
-    vec![
-        SceneType::Asteroids(AsteroidsScene::new(window_size, font48, font16))
-    ]
+fn create_scene(id: SceneId, window_size: &Vector, config: &GameConfig, ttf: &VectorFont, gfx: &Graphics) -> Result<SceneType> {
+    let scene = match id {
+        SceneId::Title => {
+            let font96 = ttf.to_renderer(gfx, 96.0)?;
+            let font24 = ttf.to_renderer(gfx, 24.0)?;
+            SceneType::Title(TitleScene::new(window_size, config, font96, font24))
+        },
+        SceneId::Asteroids => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font16 = ttf.to_renderer(gfx, 16.0)?;
+            let high_scores = HighScoreTable::load(&*platform_storage());
+            SceneType::Asteroids(AsteroidsScene::new(window_size, config, GameRng::random_seed(), high_scores, font48, font16))
+        },
+        SceneId::Pause => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font24 = ttf.to_renderer(gfx, 24.0)?;
+            SceneType::Pause(PauseScene::new(window_size, font48, font24))
+        },
+        SceneId::HighScores => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font24 = ttf.to_renderer(gfx, 24.0)?;
+            let high_scores = HighScoreTable::load(&*platform_storage());
+            SceneType::HighScores(HighScoresScene::new(window_size, high_scores, font48, font24))
+        },
+        SceneId::EnterInitials(score) => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font24 = ttf.to_renderer(gfx, 24.0)?;
+            SceneType::EnterInitials(EnterInitialsScene::new(window_size, score, platform_storage(), font48, font24))
+        },
+        SceneId::Controls => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font24 = ttf.to_renderer(gfx, 24.0)?;
+            SceneType::Controls(ControlsScene::new(window_size, platform_storage(), font48, font24))
+        },
+    };
+
+    Ok(scene)
+}
+
+fn initialize_game_scenes(window_size: &Vector, config: &GameConfig, ttf: &VectorFont, gfx: &Graphics) -> Result<Vec<SceneType>> {
+    let scene = match load_replay_from_args() {
+        Some(replay) => {
+            let font48 = ttf.to_renderer(gfx, 48.0)?;
+            let font16 = ttf.to_renderer(gfx, 16.0)?;
+            let high_scores = HighScoreTable::load(&*platform_storage());
+            SceneType::Asteroids(AsteroidsScene::from_replay(window_size, &replay, high_scores, font48, font16))
+        },
+        None => create_scene(SceneId::Title, window_size, config, ttf, gfx)?,
+    };
+
+    Ok(vec![scene])
+}
+
+// Usage: asteroids-wasm --replay <file>
+fn load_replay_from_args() -> Option<Replay> {
+    let args: Vec<String> = std::env::args().collect();
+    let index = args.iter().position(|arg| arg == "--replay")?;
+    let path = args.get(index + 1)?;
+
+    match Replay::load(path) {
+        Ok(replay) => Some(replay),
+        Err(e) => {
+            if cfg!(debug_assertions) {
+                eprintln!("Unable to load replay {}: {}", path, e);
+            }
+
+            None
+        }
+    }
 }
 
-async fn handle_input_events(input: &mut Input, state: &mut dyn Scene) {
+// Returns any touches carried by window events, to be handled along with
+// those that arrive separately
+async fn handle_input_events(input: &mut Input, state: &mut dyn Scene, bindings: &Bindings, gamepads: &mut GamepadMapper<GamepadId>,
+                             touch_source: &mut TouchSource, camera: &mut Camera, gfx: &mut Graphics) -> Vec<TouchEvent<TouchId>> {
+    let mut touches = vec![];
+
     while let Some(e) = input.next_event().await {
+        if let Some(event) = GamepadEvent::from_event(&e) {
+            handle_gamepad_event(&event, gamepads, state);
+            continue;
+        }
+
+        if let Some(event) = touch_source.from_event(&e, camera) {
+            touches.push(event);
+            continue;
+        }
+
         match e {
-            Event::KeyboardInput(key) if key.is_down() == false => state.key_up(key.key()),
+            Event::Resized(resized) => camera.resize(gfx, actual_window_size(resized.size())),
+            // Scenes that want the key itself get first refusal
+            Event::KeyboardInput(key) if !key.is_down() && !state.key_up(key.key()) => {
+                for action in bindings.actions_for(key.key()) {
+                    state.action_up(action);
+                }
+            },
+            Event::FocusChanged(focus) if !focus.is_focused() => state.focus_lost(),
             _ => { }
         }
     }
+
+    touches
 }
 
-fn update_game_scene(update_timer: &mut Timer, input: &mut Input, state: &mut dyn Scene) {
-    // We use a while loop rather than an if so that we can try to catch up in the event of having a slow down.
-    while update_timer.tick() {
-        if input.key_down(Key::Left) {
-            state.key_down(Key::Left);
-        }
+fn handle_gamepad_event(event: &GamepadEvent<GamepadId>, gamepads: &mut GamepadMapper<GamepadId>, state: &mut dyn Scene) {
+    for action in gamepads.handle(event) {
+        state.action_up(action);
+    }
 
-        if input.key_down(Key::Right) {
-            state.key_down(Key::Right);
-        }
+    // Losing the controller mid-game is as good as losing focus
+    if let GamepadEvent::Disconnected(_) = event {
+        state.focus_lost();
+    }
+}
 
-        if input.key_down(Key::Up) {
-            state.key_down(Key::Up);
+fn handle_touch_events(touches: &[TouchEvent<TouchId>], touch: &mut TouchControls<TouchId>, state: &mut dyn Scene) {
+    for event in touches {
+        for action in touch.handle(event) {
+            state.action_up(action);
         }
+    }
+}
 
-        if input.key_down(Key::Space) {
-            state.key_down(Key::Space);
+fn update_game_scene(update_timer: &mut Timer, input: &mut Input, bindings: &Bindings, gamepads: &GamepadMapper<GamepadId>, touch: &TouchControls<TouchId>, state: &mut dyn Scene) {
+    // We use a while loop rather than an if so that we can try to catch up in the event of having a slow down.
+    while update_timer.tick() {
+        for action in Action::ALL.iter() {
+            if bindings.keys(*action).iter().any(|key| input.key_down(*key)) {
+                state.action_down(*action);
+            }
         }
 
-        if input.key_down(Key::Return) {
-            state.key_down(Key::Return);
+        for action in gamepads.held().into_iter().chain(touch.held()) {
+            state.action_down(action);
         }
 
         state.update(input);
     }
 }
 
-fn render_game_scene(draw_timer: &mut Timer, window: &Window, gfx: &mut Graphics, state: &mut dyn Scene) -> Result<()> {
+fn render_game_scenes(draw_timer: &mut Timer, window: &Window, gfx: &mut Graphics, camera: &Camera, touch: &TouchControls<TouchId>, scenes: &mut [SceneType]) -> Result<()> {
     if draw_timer.exhaust().is_some() {
-        state.render(gfx)?;
+        // Walk down the stack past any overlays to find the first scene
+        // that needs to be drawn, then draw everything from there up
+        let mut bottom = scenes.len() - 1;
+        while bottom > 0 && scenes[bottom].scene().is_overlay() {
+            bottom -= 1;
+        }
+
+        for scene in scenes[bottom..].iter_mut() {
+            scene.scene().render(gfx)?;
+        }
+
+        touch.render(gfx);
+
+        camera.draw_letterbox(gfx);
+
         gfx.present(window)?;
     }
 
     Ok(())
 }
 
-fn handle_scene_transition(transition: Option<Transition>, scenes: &mut Vec<SceneType>, window_size: &Vector, font48: FontRenderer, font16: FontRenderer) {
+fn handle_scene_transition(transition: Transition, scenes: &mut Vec<SceneType>, window_size: &Vector, config: &GameConfig, ttf: &VectorFont, gfx: &Graphics) -> Result<()> {
     match transition {
-        Some(Transition::Reset) => {
+        Transition::Push(id) => {
+            scenes.push(create_scene(id, window_size, config, ttf, gfx)?);
+        },
+        Transition::Pop => {
+            scenes.pop();
+        },
+        Transition::Replace(id) => {
             scenes.pop();
-            scenes.push(SceneType::Asteroids(AsteroidsScene::new(window_size, font48, font16)))
-         },
-        None => { }
+            scenes.push(create_scene(id, window_size, config, ttf, gfx)?);
+        },
+        Transition::Reset(id) => {
+            scenes.clear();
+            scenes.push(create_scene(id, window_size, config, ttf, gfx)?);
+        },
+        Transition::Quit => {
+            scenes.clear();
+        },
     }
-}
\ No newline at end of file
+
+    Ok(())
+}
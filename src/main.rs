@@ -5,6 +5,7 @@ fn main() {
     run(
         Settings {
             title: "Asteroids",
+            resizable: true,
             ..Settings::default()
         },
         app,
@@ -0,0 +1,181 @@
+use std::io::Result;
+
+use super::storage::Storage;
+
+pub const MAX_ENTRIES: usize = 10;
+pub const INITIALS_LEN: usize = 3;
+
+const STORAGE_KEY: &str = "high_scores";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct HighScore {
+    pub initials: String,
+    pub score: i64,
+}
+
+/// The top scores, highest first.
+///
+/// Stored as one `INITIALS SCORE` pair per line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HighScoreTable {
+    entries: Vec<HighScore>,
+}
+
+impl HighScoreTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(storage: &dyn Storage) -> Self {
+        match storage.load(STORAGE_KEY) {
+            Some(text) => HighScoreTable::parse(&text),
+            None => HighScoreTable::new(),
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> Result<()> {
+        storage.save(STORAGE_KEY, &self.serialize())
+    }
+
+    /// Read a stored table, skipping any lines that have been mangled.
+    pub fn parse(text: &str) -> Self {
+        let mut table = HighScoreTable::new();
+
+        for line in text.lines() {
+            let mut parts = line.split_whitespace();
+
+            if let (Some(initials), Some(score)) = (parts.next(), parts.next()) {
+                if let Ok(score) = score.parse() {
+                    table.insert(initials, score);
+                }
+            }
+        }
+
+        table
+    }
+
+    pub fn serialize(&self) -> String {
+        self.entries.iter()
+                    .map(|e| format!("{} {}\n", e.initials, e.score))
+                    .collect()
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    /// The 1-based position `score` would take in the table, or `None` if
+    /// it doesn't make the cut.  Ties rank below existing scores.
+    pub fn rank_for(&self, score: i64) -> Option<usize> {
+        if score <= 0 {
+            return None;
+        }
+
+        let index = self.entries.iter()
+                                .position(|e| e.score < score)
+                                .unwrap_or(self.entries.len());
+
+        if index < MAX_ENTRIES { Some(index + 1) } else { None }
+    }
+
+    /// Add a score to the table, returning its rank if it placed.
+    pub fn insert(&mut self, initials: &str, score: i64) -> Option<usize> {
+        let rank = self.rank_for(score)?;
+
+        let initials: String = initials.chars()
+                                       .filter(|c| !c.is_whitespace())
+                                       .take(INITIALS_LEN)
+                                       .collect::<String>()
+                                       .to_uppercase();
+
+        self.entries.insert(rank - 1, HighScore { initials, score });
+        self.entries.truncate(MAX_ENTRIES);
+
+        Some(rank)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::persistence::storage::MemoryStorage;
+
+    fn scores(table: &HighScoreTable) -> Vec<i64> {
+        table.entries().iter().map(|e| e.score).collect()
+    }
+
+    fn full_table() -> HighScoreTable {
+        let mut table = HighScoreTable::new();
+
+        for score in 1..=MAX_ENTRIES as i64 {
+            table.insert("AAA", score * 100);
+        }
+
+        table
+    }
+
+    #[test]
+    fn keeps_scores_highest_first() {
+        let mut table = HighScoreTable::new();
+
+        assert_eq!(table.insert("abc", 200), Some(1));
+        assert_eq!(table.insert("DEF", 500), Some(1));
+        assert_eq!(table.insert("GHI", 300), Some(2));
+
+        assert_eq!(scores(&table), vec![500, 300, 200]);
+        assert_eq!(table.entries()[2].initials, "ABC");
+    }
+
+    #[test]
+    fn ties_rank_below_existing_scores() {
+        let mut table = HighScoreTable::new();
+        table.insert("OLD", 300);
+
+        assert_eq!(table.insert("NEW", 300), Some(2));
+        assert_eq!(table.entries()[0].initials, "OLD");
+        assert_eq!(table.entries()[1].initials, "NEW");
+    }
+
+    #[test]
+    fn truncates_to_max_entries() {
+        let mut table = full_table();
+
+        assert_eq!(table.insert("TOP", 10_000), Some(1));
+        assert_eq!(table.entries().len(), MAX_ENTRIES);
+        assert_eq!(table.entries().last().unwrap().score, 200);
+    }
+
+    #[test]
+    fn ranks_against_a_full_table() {
+        let table = full_table();
+
+        assert_eq!(table.rank_for(1001), Some(1));
+        assert_eq!(table.rank_for(1000), Some(2));
+        assert_eq!(table.rank_for(101), Some(MAX_ENTRIES));
+        assert_eq!(table.rank_for(100), None);
+        assert_eq!(table.rank_for(50), None);
+        assert_eq!(HighScoreTable::new().rank_for(0), None);
+    }
+
+    #[test]
+    fn round_trips_through_storage() {
+        let mut storage = MemoryStorage::new();
+        let table = full_table();
+
+        table.save(&mut storage).unwrap();
+
+        assert_eq!(HighScoreTable::load(&storage), table);
+        assert_eq!(HighScoreTable::load(&MemoryStorage::new()), HighScoreTable::new());
+    }
+
+    #[test]
+    fn skips_mangled_lines() {
+        let mut storage = MemoryStorage::new();
+        storage.save(STORAGE_KEY, "ABC 300\ngarbage\nDEF\nGHI lots\n\nJKL 100 extra\nMN").unwrap();
+
+        let table = HighScoreTable::load(&storage);
+
+        assert_eq!(scores(&table), vec![300, 100]);
+        assert_eq!(table.entries()[1].initials, "JKL");
+    }
+}
@@ -0,0 +1,2 @@
+pub mod high_scores;
+pub mod storage;
@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::io::Result;
+
+/// A simple key/value store for anything the game needs to remember between
+/// sessions.  Values are plain strings so backends don't need to know what
+/// they are storing.
+pub trait Storage {
+    fn load(&self, key: &str) -> Option<String>;
+    fn save(&mut self, key: &str, value: &str) -> Result<()>;
+}
+
+/// Storage for the platform the game is running on: files on native builds
+/// and `localStorage` in the browser.
+pub fn platform_storage() -> Box<dyn Storage> {
+    #[cfg(target_arch = "wasm32")]
+    let storage = LocalStorage::new("asteroids");
+
+    #[cfg(not(target_arch = "wasm32"))]
+    let storage = FileStorage::new("save");
+
+    Box::new(storage)
+}
+
+/// Keeps everything in memory.  Nothing survives the process exiting, which
+/// makes it handy for tests and headless runs.
+#[derive(Default)]
+pub struct MemoryStorage {
+    values: HashMap<String, String>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<()> {
+        self.values.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+}
+
+/// Stores each key as a text file inside a directory.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct FileStorage {
+    directory: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    pub fn new<P: Into<std::path::PathBuf>>(directory: P) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        self.directory.join(format!("{}.txt", key))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        std::fs::read_to_string(self.path(key)).ok()
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<()> {
+        std::fs::create_dir_all(&self.directory)?;
+        std::fs::write(self.path(key), value)
+    }
+}
+
+/// Stores each key in the browser's `localStorage`, namespaced by a prefix.
+#[cfg(target_arch = "wasm32")]
+pub struct LocalStorage {
+    prefix: String,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl LocalStorage {
+    pub fn new(prefix: &str) -> Self {
+        Self {
+            prefix: prefix.to_string(),
+        }
+    }
+
+    fn key(&self, key: &str) -> String {
+        format!("{}.{}", self.prefix, key)
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Storage for LocalStorage {
+    fn load(&self, key: &str) -> Option<String> {
+        stdweb::web::window().local_storage().get(&self.key(key))
+    }
+
+    fn save(&mut self, key: &str, value: &str) -> Result<()> {
+        stdweb::web::window().local_storage()
+                             .insert(&self.key(key), value)
+                             .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "localStorage is full or disabled"))
+    }
+}
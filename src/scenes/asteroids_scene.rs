@@ -1,117 +1,142 @@
 use quicksilver::{
     geom::Vector,
-    input::Key,
     graphics::{Color, FontRenderer},
     Graphics, Input, Result,
 };
 
-use super::scene::{Scene, Transition};
-use super::game_objects::player::Player;
-use super::game_objects::asteroids::{Asteroid, Sizes};
+use super::scene::{Scene, SceneId, Transition};
+use crate::controls::action::Action;
 use super::game_objects::hud::Hud;
 use super::game_objects::game_object::GameObject;
 use super::game_objects::star_field::StarField;
-
-const NUM_ASTEROIDS: u8 = 27;
-const NUM_SPAWN_ASTEROIDS: i32 = 3;
+use crate::config::game_config::GameConfig;
+use crate::persistence::high_scores::HighScoreTable;
+#[cfg(target_arch = "wasm32")]
+use crate::persistence::storage::platform_storage;
+use crate::sim::inputs::Inputs;
+use crate::sim::replay::{Playback, Replay};
+use crate::sim::simulation::Simulation;
+use crate::util::rng::GameRng;
+
+// Every game is recorded so it can be attached to bug reports, to a file on
+// native builds and to storage on the web
+#[cfg(not(target_arch = "wasm32"))]
+const REPLAY_FILE: &str = "last_game.replay";
+#[cfg(target_arch = "wasm32")]
+const REPLAY_KEY: &str = "last_game_replay";
 
 pub struct AsteroidsScene {
-    window_size: Vector,
-    player: Player,
-    asteroids: Vec<Asteroid>,
+    sim: Simulation,
+    inputs: Inputs,
+    recorder: Replay,
+    playback: Option<Playback>,
+    replay_saved: bool,
+    high_scores: HighScoreTable,
+    rank: Option<usize>,
     hud: Hud,
-    score: i64,
     star_field: StarField,
     transition: Option<Transition>
 }
 
 impl AsteroidsScene {
-    pub fn new(window_size: &Vector, font48: FontRenderer, font16: FontRenderer) -> Self {
+    pub fn new(window_size: &Vector, config: &GameConfig, seed: u64, high_scores: HighScoreTable, font48: FontRenderer, font16: FontRenderer) -> Self {
+        AsteroidsScene::create(window_size, config, seed, None, high_scores, font48, font16)
+    }
+
+    /// Create a scene that plays back a recorded game instead of reading the
+    /// keyboard, with the config it was recorded with rather than the current one.
+    pub fn from_replay(window_size: &Vector, replay: &Replay, high_scores: HighScoreTable, font48: FontRenderer, font16: FontRenderer) -> Self {
+        AsteroidsScene::create(window_size, replay.config(), replay.seed(), Some(replay.playback()), high_scores, font48, font16)
+    }
+
+    fn create(window_size: &Vector, config: &GameConfig, seed: u64, playback: Option<Playback>, high_scores: HighScoreTable, font48: FontRenderer, font16: FontRenderer) -> Self {
         AsteroidsScene {
-            window_size: window_size.clone(),
-            player: Player::new(&window_size),
-            asteroids: AsteroidsScene::initialize_asteroids(window_size),
-            hud: Hud::new(font48, font16),
-            score: 0,
-            star_field: StarField::new(window_size),
+            sim: Simulation::new(window_size, config, seed),
+            inputs: Inputs::new(),
+            recorder: Replay::new(seed, config),
+            playback,
+            replay_saved: false,
+            high_scores,
+            rank: None,
+            hud: Hud::new(window_size, font48, font16),
+            // The star field gets its own generator so that purely cosmetic
+            // randomness never shifts the simulation's random sequence
+            star_field: StarField::new(window_size, &mut GameRng::new(seed)),
             transition: None,
         }
     }
 
-    fn initialize_asteroids(window_size: &Vector) -> Vec<Asteroid> {
-        (0..NUM_ASTEROIDS).map(|i| Asteroid::new(&window_size, i < 3)).collect()
+    fn next_inputs(&mut self) -> Inputs {
+        match &mut self.playback {
+            Some(playback) => playback.next().unwrap_or_default(),
+            None => self.inputs,
+        }
     }
-}
 
-impl Scene for AsteroidsScene {
-    fn update(&mut self, _input: &mut Input) {
-        let mut spawn_queue: Vec<(Sizes, Vector)> = vec![];
+    fn pause(&mut self) {
+        // There's nothing left to pause once the game is over
+        if !self.sim.is_game_over() && self.transition.is_none() {
+            self.transition = Some(Transition::Push(SceneId::Pause));
+        }
+    }
+
+    fn game_over_confirmed(&mut self) {
+        self.transition = match self.rank {
+            Some(_) => Some(Transition::Push(SceneId::EnterInitials(self.sim.score))),
+            None => Some(Transition::Replace(SceneId::Asteroids)),
+        };
+    }
 
-        // Update Player
-        self.player.update();
-        self.player.check_bounds();
+    fn save_replay(&mut self) {
+        if self.replay_saved {
+            return;
+        }
 
+        self.replay_saved = true;
 
-        // If all asteroids are destroyed, re-initialize level
-        if self.asteroids.iter().all(|a| a.is_dead()) {
-            self.asteroids = AsteroidsScene::initialize_asteroids(&self.window_size);
+        // Never overwrite a recording with the playback of itself
+        if self.playback.is_some() {
+            return;
         }
 
-        // Check for Collisions
-        for asteroid in self.asteroids.iter_mut() {
-            if asteroid.is_dead() {
-                continue;
-            }
+        // Losing the recording isn't worth interrupting the game over for
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = self.recorder.save(REPLAY_FILE);
 
-            // Handle Collision Between Player and Asteroid
-            if asteroid.check_collision(self.player.location, self.player.hit_radius) {
-                self.player.handle_collsion();
-            }
+        #[cfg(target_arch = "wasm32")]
+        let _ = platform_storage().save(REPLAY_KEY, &self.recorder.to_text());
+    }
+}
 
-            // Handle Collision Between Bullet and Asteroid
-            for bullet in self.player.bullets.iter_mut() {
-                if bullet.is_alive() && asteroid.check_collision(bullet.location - self.player.translation, 1.0) {
-                    asteroid.handle_collision();
-                    bullet.handle_collision();
-
-                    self.score += match asteroid.size {
-                        Sizes::Large => 50,
-                        Sizes::Medium => 100,
-                        Sizes::Small => 200
-                    };
-
-                    // If an asteroid is destroyed, queue a smaller version to be spawned
-                    if asteroid.size != Sizes::Small {
-                        spawn_queue.push((asteroid.size, asteroid.location));
-                    }
-                }
-            }
+impl Scene for AsteroidsScene {
+    fn update(&mut self, _input: &mut Input) {
+        // Advance the simulation with the inputs gathered since the last tick,
+        // or the next recorded inputs when playing back a replay
+        let inputs = self.next_inputs();
+        self.inputs = Inputs::new();
+
+        if !self.sim.is_game_over() {
+            self.recorder.record(&inputs);
         }
 
-        // Spawn Smaller Asteroids
-        while let Some((size, location)) = spawn_queue.pop() {
-            let mut spawn_count = 0;
+        self.sim.step(&inputs);
 
-            // Reuse dead asteroids for spawning
-            for asteroid in self.asteroids.iter_mut() {
-                if asteroid.is_alive() { continue; }
+        if self.sim.is_game_over() && !self.replay_saved {
+            self.save_replay();
 
-                if spawn_count < NUM_SPAWN_ASTEROIDS {
-                    asteroid.spawn_asteroid(&location, &size);
-                    spawn_count += 1;
-                }
+            // Replays don't get to claim a spot in the high score table
+            if self.playback.is_none() {
+                self.rank = self.high_scores.rank_for(self.sim.score);
             }
         }
 
-        // Update Asteroids
-        for asteroid in self.asteroids.iter_mut() {
-            asteroid.update();
-            asteroid.check_bounds();
-        }
-
         // Update Hud
-        self.hud.set_lives(self.player.lives);
-        self.hud.set_score(self.score);
+        self.hud.set_lives(self.sim.lives());
+        self.hud.set_score(self.sim.score);
+        self.hud.set_rank(self.rank);
+        self.hud.set_hyperspace_charge(self.sim.player.hyperspace_charge());
+        self.hud.set_ready(self.sim.player.is_respawning());
+        self.hud.set_wave(self.sim.wave(), self.sim.is_between_waves());
         self.hud.update();
     }
 
@@ -123,44 +148,61 @@ impl Scene for AsteroidsScene {
         self.star_field.render(gfx)?;
 
         // Render player and bullets
-        self.player.render(gfx)?;
+        self.sim.player.render(gfx)?;
 
         // Render asteroids
-        for asteroid in self.asteroids.iter_mut() {
+        for asteroid in self.sim.asteroids.iter_mut() {
             asteroid.render(gfx)?;
         }
 
+        // Render saucer and its bullets
+        self.sim.saucer.render(gfx)?;
+
+        // Render explosions and exhaust
+        self.sim.particles.render(gfx)?;
+
         // Render hud
         self.hud.render(gfx)?;
 
         Ok(())
     }
 
-    fn key_down(&mut self, key: Key) {
-        match key {
-            Key::Left => self.player.rotate(-4.0),
-            Key::Right => self.player.rotate(4.0),
-            Key::Up => self.player.apply_thrust(),
-            Key::Return if !self.player.is_alive() => self.transition = Some(Transition::Reset),
+    fn action_down(&mut self, action: Action) {
+        match action {
+            Action::RotateLeft => self.inputs.rotate_left = true,
+            Action::RotateRight => self.inputs.rotate_right = true,
+            Action::Thrust => self.inputs.thrust = true,
+            Action::Fire => self.inputs.fire_held = true,
             _ => { }
         }
     }
 
-    fn key_up(&mut self, key: Key) {
-        if key == Key::Space {
-            self.player.shoot_bullet();
+    fn action_up(&mut self, action: Action) {
+        match action {
+            Action::Fire => self.inputs.fire = true,
+            Action::Hyperspace => self.inputs.hyperspace = true,
+            Action::Pause => self.pause(),
+            Action::Confirm if self.sim.is_game_over() => self.game_over_confirmed(),
+            _ => { }
         }
     }
 
+    fn focus_lost(&mut self) {
+        self.pause();
+    }
+
     fn should_transition(&self) -> bool {
-        if let Some(_) = &self.transition {
-            true
-        } else {
-            false
-        }
+        self.transition.is_some()
     }
 
-    fn get_transition(&self) -> Option<Transition> {
-        self.transition.clone()
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
     }
-}
\ No newline at end of file
+}
+
+impl Drop for AsteroidsScene {
+    fn drop(&mut self) {
+        // Games abandoned from the pause menu are still worth keeping
+        self.save_replay();
+    }
+}
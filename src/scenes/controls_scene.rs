@@ -0,0 +1,167 @@
+use quicksilver::{
+    geom::Vector,
+    input::Key,
+    graphics::{Color, FontRenderer},
+    Graphics, Input, Result,
+};
+
+use super::scene::{Scene, Transition};
+use super::game_objects::menu::centered;
+use crate::controls::action::Action;
+use crate::controls::bindings::{self, Bindings};
+use crate::persistence::storage::Storage;
+use crate::v;
+
+const TITLE_SIZE: f32 = 48.0;
+const TEXT_SIZE: f32 = 24.0;
+const ROW_SPACING: f32 = 34.0;
+
+// Rows after the actions
+const RESET_ROW: usize = Action::ALL.len();
+const BACK_ROW: usize = Action::ALL.len() + 1;
+
+/// The options screen, which for now holds the controls.  Lists every
+/// action with its keys.  Confirming an action waits for the next key press
+/// and adds it to the action.
+pub struct ControlsScene {
+    window_size: Vector,
+    bindings: Bindings,
+    storage: Box<dyn Storage>,
+    selected: usize,
+    listening: bool,
+    font_title: FontRenderer,
+    font_text: FontRenderer,
+    transition: Option<Transition>,
+}
+
+impl ControlsScene {
+    pub fn new(window_size: &Vector, storage: Box<dyn Storage>, font_title: FontRenderer, font_text: FontRenderer) -> Self {
+        Self {
+            window_size: *window_size,
+            bindings: Bindings::load(&*storage),
+            storage,
+            selected: 0,
+            listening: false,
+            font_title,
+            font_text,
+            transition: None,
+        }
+    }
+
+    fn select(&mut self, step: isize) {
+        let count = BACK_ROW as isize + 1;
+        self.selected = (self.selected as isize + step).rem_euclid(count) as usize;
+    }
+
+    fn activate(&mut self) {
+        match self.selected {
+            RESET_ROW => {
+                self.bindings.reset();
+                self.save();
+            },
+            BACK_ROW => self.transition = Some(Transition::Pop),
+            _ => self.listening = true,
+        }
+    }
+
+    // The new bindings still apply for this session if they can't be kept
+    fn save(&mut self) {
+        let _ = self.bindings.save(&mut *self.storage);
+    }
+}
+
+impl Scene for ControlsScene {
+    fn update(&mut self, _input: &mut Input) { }
+
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        gfx.clear(Color::BLACK);
+
+        let center_x = self.window_size.x / 2.0;
+        let top = self.window_size.y / 8.0;
+        let dim = Color::from_rgba(255, 255, 255, 0.6);
+
+        let title = "OPTIONS";
+        self.font_title.draw(gfx, title, Color::WHITE, centered(title, TITLE_SIZE, center_x, top))?;
+
+        let label_x = center_x - 320.0;
+        let keys_x = center_x - 20.0;
+
+        for (i, action) in Action::ALL.iter().enumerate() {
+            let y = top + 60.0 + i as f32 * ROW_SPACING;
+            let color = if i == self.selected { Color::WHITE } else { dim };
+            let marker = if i == self.selected { "> " } else { "  " };
+
+            self.font_text.draw(gfx, &format!("{}{}", marker, action.label()), color, v!(label_x, y))?;
+
+            let keys = if i == self.selected && self.listening {
+                "PRESS A KEY...".to_string()
+            } else {
+                let names: Vec<String> = self.bindings.keys(*action).iter().map(|k| bindings::key_name(*k).to_uppercase()).collect();
+                names.join(", ")
+            };
+
+            self.font_text.draw(gfx, &keys, color, v!(keys_x, y))?;
+        }
+
+        for (row, label) in [(RESET_ROW, "RESET DEFAULTS"), (BACK_ROW, "BACK")].iter() {
+            let y = top + 80.0 + *row as f32 * ROW_SPACING;
+            let label = if *row == self.selected { format!("> {} <", label) } else { label.to_string() };
+            let color = if *row == self.selected { Color::WHITE } else { dim };
+
+            self.font_text.draw(gfx, &label, color, centered(&label, TEXT_SIZE, center_x, y))?;
+        }
+
+        let hint = if self.listening {
+            "ESC CANCEL   BACKSPACE REMOVE LAST KEY"
+        } else {
+            "ENTER ADD KEY   ESC BACK"
+        };
+        let y = top + 120.0 + (BACK_ROW + 1) as f32 * ROW_SPACING;
+        self.font_text.draw(gfx, hint, dim, centered(hint, TEXT_SIZE, center_x, y))?;
+
+        Ok(())
+    }
+
+    fn key_up(&mut self, key: Key) -> bool {
+        if !self.listening {
+            return false;
+        }
+
+        let action = Action::ALL[self.selected];
+
+        // Escape and backspace are reserved for getting out of a mistake
+        match key {
+            Key::Escape => { },
+            Key::Back => {
+                self.bindings.unbind_last(action);
+                self.save();
+            },
+            _ => {
+                self.bindings.bind(action, key);
+                self.save();
+            },
+        }
+
+        self.listening = false;
+
+        true
+    }
+
+    fn action_up(&mut self, action: Action) {
+        match action {
+            Action::MenuUp => self.select(-1),
+            Action::MenuDown => self.select(1),
+            Action::Confirm => self.activate(),
+            Action::Cancel => self.transition = Some(Transition::Pop),
+            _ => { }
+        }
+    }
+
+    fn should_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
+    }
+}
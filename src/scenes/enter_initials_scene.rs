@@ -0,0 +1,143 @@
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, FontRenderer},
+    Graphics, Input, Result,
+};
+
+use super::scene::{Scene, SceneId, Transition};
+use crate::controls::action::Action;
+use super::game_objects::menu::centered;
+use crate::persistence::high_scores::{HighScoreTable, INITIALS_LEN};
+use crate::persistence::storage::Storage;
+use crate::v;
+
+const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const TITLE_SIZE: f32 = 48.0;
+const TEXT_SIZE: f32 = 24.0;
+const SLOT_WIDTH: f32 = 48.0;
+
+/// Arcade style initials entry, shown over the game once it's over and the
+/// score has made the high score table.
+pub struct EnterInitialsScene {
+    window_size: Vector,
+    score: i64,
+    rank: Option<usize>,
+    letters: [usize; INITIALS_LEN],
+    cursor: usize,
+    high_scores: HighScoreTable,
+    storage: Box<dyn Storage>,
+    font_title: FontRenderer,
+    font_text: FontRenderer,
+    transition: Option<Transition>,
+}
+
+impl EnterInitialsScene {
+    pub fn new(window_size: &Vector, score: i64, storage: Box<dyn Storage>, font_title: FontRenderer, font_text: FontRenderer) -> Self {
+        let high_scores = HighScoreTable::load(&*storage);
+
+        Self {
+            window_size: *window_size,
+            score,
+            rank: high_scores.rank_for(score),
+            letters: [0; INITIALS_LEN],
+            cursor: 0,
+            high_scores,
+            storage,
+            font_title,
+            font_text,
+            transition: None,
+        }
+    }
+
+    fn initials(&self) -> String {
+        self.letters.iter().map(|i| LETTERS[*i] as char).collect()
+    }
+
+    fn cycle_letter(&mut self, step: isize) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = (*letter as isize + step).rem_euclid(LETTERS.len() as isize) as usize;
+    }
+
+    fn next_slot(&mut self) {
+        if self.cursor + 1 < INITIALS_LEN {
+            self.cursor += 1;
+        } else {
+            self.submit();
+        }
+    }
+
+    fn submit(&mut self) {
+        self.high_scores.insert(&self.initials(), self.score);
+
+        // The table still shows the score even if it can't be kept
+        let _ = self.high_scores.save(&mut *self.storage);
+
+        self.transition = Some(Transition::Reset(SceneId::HighScores));
+    }
+}
+
+impl Scene for EnterInitialsScene {
+    fn update(&mut self, _input: &mut Input) { }
+
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        let center_x = self.window_size.x / 2.0;
+        let center_y = self.window_size.y / 2.0;
+
+        let overlay = Rectangle::new(Vector::ZERO, self.window_size);
+        gfx.fill_rect(&overlay, Color::from_rgba(0, 0, 0, 0.7));
+
+        let title = "NEW HIGH SCORE!";
+        self.font_title.draw(gfx, title, Color::WHITE, centered(title, TITLE_SIZE, center_x, center_y - 120.0))?;
+
+        if let Some(rank) = self.rank {
+            let text = format!("RANK #{}   SCORE {}", rank, self.score);
+            self.font_text.draw(gfx, &text, Color::WHITE, centered(&text, TEXT_SIZE, center_x, center_y - 70.0))?;
+        }
+
+        // Draw each letter in its own slot, underlining the one being edited
+        let left = center_x - SLOT_WIDTH * INITIALS_LEN as f32 / 2.0;
+
+        for (i, letter) in self.initials().chars().enumerate() {
+            let x = left + i as f32 * SLOT_WIDTH;
+            let color = if i == self.cursor { Color::WHITE } else { Color::from_rgba(255, 255, 255, 0.6) };
+
+            self.font_title.draw(gfx, &letter.to_string(), color, v!(x + SLOT_WIDTH / 4.0, center_y + 20.0))?;
+
+            if i == self.cursor {
+                let underline = Rectangle::new(v!(x + 6.0, center_y + 32.0), v!(SLOT_WIDTH - 12.0, 3.0));
+                gfx.fill_rect(&underline, Color::WHITE);
+            }
+        }
+
+        let hint = "UP/DOWN CHANGE   LEFT/RIGHT MOVE   ENTER CONFIRM";
+        self.font_text.draw(gfx, hint, Color::from_rgba(255, 255, 255, 0.6), centered(hint, TEXT_SIZE, center_x, center_y + 110.0))?;
+
+        Ok(())
+    }
+
+    fn action_up(&mut self, action: Action) {
+        if self.transition.is_some() {
+            return;
+        }
+
+        match action {
+            Action::MenuUp => self.cycle_letter(1),
+            Action::MenuDown => self.cycle_letter(-1),
+            Action::MenuLeft | Action::Cancel => self.cursor = self.cursor.saturating_sub(1),
+            Action::MenuRight | Action::Confirm => self.next_slot(),
+            _ => { }
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+
+    fn should_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
+    }
+}
@@ -1,19 +1,27 @@
 use quicksilver::{
     graphics::{Color, Graphics, FontRenderer},
-    geom::Vector,
+    geom::{Rectangle, Vector},
     Result
 };
 
 use super::game_object::GameObject;
+use super::menu::centered;
 use super::util::math::VectorMath;
 use crate::v;
 
 const MARGIN: f32 = 20.0;
 const CHAR_WIDTH: f32 = 8.0;
+const CHARGE_BAR_WIDTH: f32 = 60.0;
 
 pub struct Hud {
+    window_size: Vector,
     player_lives: i32,
     score: i64,
+    rank: Option<usize>,
+    hyperspace_charge: f32,
+    ready: bool,
+    wave: u32,
+    wave_banner: bool,
     object_vertices: Vec<Vector>,
     font48: FontRenderer,
     font16: FontRenderer,
@@ -21,7 +29,7 @@ pub struct Hud {
 }
 
 impl Hud {
-    pub fn new(font48: FontRenderer, font16: FontRenderer) -> Self {
+    pub fn new(window_size: &Vector, font48: FontRenderer, font16: FontRenderer) -> Self {
         let object_vertices: Vec<Vector> = vec![v!(0.0, 1.5), v!(-1.0, -1.0), v!(1.0, -1.0), v!(0.0, 1.5)];
 
         let object_vertices = object_vertices.iter()
@@ -29,8 +37,14 @@ impl Hud {
                                              .collect();
 
         Self {
+            window_size: *window_size,
             player_lives: 0,
             score: 0,
+            rank: None,
+            hyperspace_charge: 1.0,
+            ready: false,
+            wave: 1,
+            wave_banner: false,
             object_vertices,
             font48,
             font16,
@@ -46,6 +60,26 @@ impl Hud {
         self.score = score;
     }
 
+    /// Where the final score placed in the high score table, if it did.
+    pub fn set_rank(&mut self, rank: Option<usize>) {
+        self.rank = rank;
+    }
+
+    pub fn set_hyperspace_charge(&mut self, charge: f32) {
+        self.hyperspace_charge = charge;
+    }
+
+    /// Show the "READY" prompt while the next ship waits to launch.
+    pub fn set_ready(&mut self, ready: bool) {
+        self.ready = ready;
+    }
+
+    /// The current wave, and whether to announce it in the middle of the screen.
+    pub fn set_wave(&mut self, wave: u32, show_banner: bool) {
+        self.wave = wave;
+        self.wave_banner = show_banner;
+    }
+
     fn build_ship_icon(&self, location: Vector) -> Vec<Vector> {
         self.object_vertices.iter().map(|x| *x + location).collect()
     }
@@ -71,17 +105,37 @@ impl GameObject for Hud {
             gfx.fill_polygon(&icon, Color::WHITE);
         }
 
+        // Hyperspace charge, dimmed until it's ready to use
+        let ready = self.hyperspace_charge >= 1.0;
+        let color = if ready { Color::WHITE } else { Color::from_rgba(255, 255, 255, 0.5) };
+
+        self.font16.draw(
+            gfx,
+            "HYPER",
+            color,
+            v!(14.0, 72.0)
+        )?;
+
+        let bar = Rectangle::new(v!(70.0, 62.0), v!(CHARGE_BAR_WIDTH, 8.0));
+        let charge = Rectangle::new(bar.pos, v!(CHARGE_BAR_WIDTH * self.hyperspace_charge, 8.0));
+
+        gfx.stroke_rect(&bar, color);
+        gfx.fill_rect(&charge, color);
+
         // Write out Score Label
+        let label = "SCORE";
+        let x = self.window_size.x - (label.len() as f32) * CHAR_WIDTH - MARGIN;
+
         self.font16.draw(
             gfx,
-            "SCORE",
+            label,
             Color::WHITE,
-            v!(965.0, 24.0)
+            v!(x, 24.0)
         )?;
 
         // Right Align Score
         let score_str = &format!("{}", self.score);
-        let x = 1024.0 - (score_str.len() as f32) * CHAR_WIDTH - MARGIN;
+        let x = self.window_size.x - (score_str.len() as f32) * CHAR_WIDTH - MARGIN;
         let y = MARGIN * 2.0;
 
         self.font16.draw(
@@ -91,28 +145,72 @@ impl GameObject for Hud {
             v!(x, y),
         )?;
 
+        let center = self.window_size.divide(2.0);
+
+        // Wave number, top centre
+        let wave_str = format!("WAVE {}", self.wave);
+
+        self.font16.draw(
+            gfx,
+            &wave_str,
+            Color::WHITE,
+            centered(&wave_str, 16.0, center.x, 24.0)
+        )?;
+
+        if self.wave_banner && self.player_lives > 0 {
+            self.font48.draw(
+                gfx,
+                &wave_str,
+                Color::WHITE,
+                centered(&wave_str, 48.0, center.x, center.y - 150.0)
+            )?;
+        }
+
+        if self.ready && self.player_lives > 0 {
+            self.font48.draw(
+                gfx,
+                "READY",
+                Color::WHITE,
+                centered("READY", 48.0, center.x, center.y - 84.0)
+            )?;
+        }
+
         if self.player_lives == 0 {
+            let game_over = "Game Over!";
+
             self.font48.draw(
                 gfx,
-                "Game Over!",
+                game_over,
                 Color::from_rgba(255, 255, 255, if self.alpha < 0.5 { self.alpha * 2.0 } else { 1.0 }),
-                Vector::new(400.0, 397.0),
+                centered(game_over, 48.0, center.x, center.y + 13.0),
+            )?;
+
+            let your_score = format!("YOUR SCORE: {}", self.score);
+
+            self.font16.draw(
+                gfx,
+                &your_score,
+                Color::from_rgba(255, 255, 255, self.alpha),
+                centered(&your_score, 16.0, center.x, center.y + 51.0)
             )?;
 
-            let x = 466.0 - ((score_str.len() as f32) * CHAR_WIDTH / 2.0);
+            let (placement, prompt) = match self.rank {
+                Some(rank) => (format!("NEW HIGH SCORE! RANK #{}", rank), "Press ENTER To Enter Initials"),
+                None => ("NO HIGH SCORE THIS TIME".to_string(), "Press ENTER To Restart"),
+            };
 
             self.font16.draw(
                 gfx,
-                &format!("YOUR SCORE: {}", self.score),
+                &placement,
                 Color::from_rgba(255, 255, 255, self.alpha),
-                v!(x, 435.0)
+                centered(&placement, 16.0, center.x, center.y + 86.0)
             )?;
 
             self.font16.draw(
                 gfx,
-                &format!("Press ENTER To Restart"),
+                prompt,
                 Color::from_rgba(255, 255, 255, self.alpha),
-                v!(428.0, 520.0)
+                centered(prompt, 16.0, center.x, center.y + 136.0)
             )?;
         }
 
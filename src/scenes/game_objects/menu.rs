@@ -0,0 +1,121 @@
+use quicksilver::{
+    graphics::{Color, Graphics, FontRenderer},
+    geom::Vector,
+    Result
+};
+
+use super::game_object::GameObject;
+use crate::controls::action::Action;
+use crate::scenes::scene::Transition;
+use crate::v;
+
+// ShareTechMono glyphs advance roughly half of the font size
+const CHAR_ASPECT: f32 = 0.5;
+
+/// Location to draw `text` so that it is horizontally centered on `center_x`.
+pub fn centered(text: &str, font_size: f32, center_x: f32, y: f32) -> Vector {
+    let width = text.len() as f32 * font_size * CHAR_ASPECT;
+    v!(center_x - width / 2.0, y)
+}
+
+pub struct MenuItem {
+    pub label: &'static str,
+    pub transition: Option<Transition>,
+}
+
+impl MenuItem {
+    pub fn new(label: &'static str, transition: Option<Transition>) -> Self {
+        Self { label, transition }
+    }
+
+    /// Items without a transition are shown greyed out and can't be selected.
+    pub fn is_enabled(&self) -> bool {
+        self.transition.is_some()
+    }
+}
+
+/// A vertical list of choices navigated with the menu actions.
+pub struct Menu {
+    items: Vec<MenuItem>,
+    selected: usize,
+    font: FontRenderer,
+    font_size: f32,
+    center_x: f32,
+    top: f32,
+    spacing: f32,
+}
+
+impl Menu {
+    pub fn new(items: Vec<MenuItem>, font: FontRenderer, font_size: f32, center_x: f32, top: f32) -> Self {
+        Self {
+            items,
+            selected: 0,
+            font,
+            font_size,
+            center_x,
+            top,
+            spacing: font_size * 1.8,
+        }
+    }
+
+    pub fn select_previous(&mut self) {
+        self.move_selection(-1);
+    }
+
+    pub fn select_next(&mut self) {
+        self.move_selection(1);
+    }
+
+    fn move_selection(&mut self, step: isize) {
+        let count = self.items.len() as isize;
+        let mut index = self.selected as isize;
+
+        // Skip over anything that can't be selected
+        loop {
+            index = (index + step).rem_euclid(count);
+
+            if self.items[index as usize].is_enabled() || index as usize == self.selected {
+                break;
+            }
+        }
+
+        self.selected = index as usize;
+    }
+
+    pub fn selected_transition(&self) -> Option<Transition> {
+        self.items[self.selected].transition
+    }
+
+    /// Navigate up and down, returning the chosen transition when the
+    /// selection is confirmed.
+    pub fn action(&mut self, action: Action) -> Option<Transition> {
+        match action {
+            Action::MenuUp => self.select_previous(),
+            Action::MenuDown => self.select_next(),
+            Action::Confirm => return self.selected_transition(),
+            _ => { }
+        }
+
+        None
+    }
+}
+
+impl GameObject for Menu {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        for (i, item) in self.items.iter().enumerate() {
+            let label = if i == self.selected { format!("> {} <", item.label) } else { item.label.to_string() };
+            let color = if !item.is_enabled() {
+                Color::from_rgba(255, 255, 255, 0.25)
+            } else if i == self.selected {
+                Color::WHITE
+            } else {
+                Color::from_rgba(255, 255, 255, 0.6)
+            };
+
+            let location = centered(&label, self.font_size, self.center_x, self.top + (i as f32) * self.spacing);
+            self.font.draw(gfx, &label, color, location)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,7 @@
-pub mod asteroids;
-pub mod bullet;
 pub mod game_object;
 pub mod hud;
-pub mod player;
+pub mod menu;
+pub mod render;
 pub mod star_field;
 
-use super::util;
\ No newline at end of file
+use super::util;
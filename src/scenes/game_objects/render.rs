@@ -0,0 +1,132 @@
+use quicksilver::{
+    graphics::{Color, Graphics},
+    geom::{Circle, Vector},
+    Result
+};
+
+use super::game_object::GameObject;
+use super::util::math;
+use crate::sim::objects::asteroids::Asteroid;
+use crate::sim::objects::bullet::Bullet;
+use crate::sim::objects::components::{Collider, Renderable, Transform, Wrap};
+use crate::sim::objects::particles::{Particles, Shape};
+use crate::sim::objects::player::{Player, WARP_RADIUS};
+use crate::sim::objects::saucer::Saucer;
+
+// How the simulated objects look.  The simulation only keeps their state and
+// steps them through their own `update`, so all the drawing lives here.
+
+// Ticks over which a bullet fades out at the end of its range
+const FADE_TICKS: i32 = 8;
+
+/// Draw the placed outline, with a copy across whichever edges it hangs over.
+fn draw(gfx: &mut Graphics, renderable: &Renderable, transform: &Transform, collider: &Collider, wrap: Wrap) {
+    gfx.stroke_polygon(&renderable.world_vertices, renderable.color);
+
+    let offsets = math::ghost_offsets(transform.location, collider.bounding_radius, transform.playfield());
+
+    // Things that only wrap vertically only show up again vertically
+    for offset in offsets.into_iter().filter(|offset| wrap != Wrap::Vertical || offset.x == 0.0) {
+        let ghost: Vec<Vector> = renderable.world_vertices.iter().map(|x| *x + offset).collect();
+        gfx.stroke_polygon(&ghost, renderable.color);
+    }
+}
+
+impl GameObject for Asteroid {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        if self.alive {
+            draw(gfx, &self.renderable, &self.transform, &self.collider, self.wrap);
+
+            // DEBUG: Collision Circle For Debugging
+            // let circle = Circle::new(self.transform.screen(), self.collider.hit_radius);
+            // gfx.stroke_circle(&circle, Color::RED);
+        }
+
+        Ok(())
+    }
+}
+
+impl GameObject for Bullet {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        let alpha = (self.lifetime.ticks_left as f32 / FADE_TICKS as f32).min(1.0);
+        let circle = Circle::new(self.transform.location, 1.5);
+
+        gfx.fill_circle(&circle, Color::from_rgba(171, 235, 198, alpha));
+
+        Ok(())
+    }
+}
+
+impl GameObject for Player {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        // Warp-out rings grow and fade where the ship left
+        for (location, radius) in self.warp_out.iter() {
+            let circle = Circle::new(*location + self.transform.translation, *radius);
+            let alpha = 1.0 - radius / WARP_RADIUS;
+
+            gfx.stroke_circle(&circle, Color::from_rgba(127, 179, 213, alpha));
+        }
+
+        // Warp-in ring closes in on the destination until the ship appears
+        if let Some(radius) = self.warp_in_radius() {
+            let circle = Circle::new(self.transform.screen(), radius);
+
+            gfx.stroke_circle(&circle, Color::from_rgba(127, 179, 213, 1.0 - radius / WARP_RADIUS));
+        }
+
+        if self.is_visible() {
+            draw(gfx, &self.renderable, &self.transform, &self.collider, self.wrap);
+
+            // DEBUG: Collision Circle For Debugging
+            // let circle = Circle::new(self.transform.screen(), self.collider.hit_radius);
+            // gfx.stroke_circle(&circle, Color::BLUE);
+        }
+
+        for bullet in self.bullets.iter_mut().filter(|x| x.alive) {
+            bullet.render(gfx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl GameObject for Saucer {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        if self.alive {
+            draw(gfx, &self.renderable, &self.transform, &self.collider, self.wrap);
+
+            // Seams between the dome and the hull
+            let (vertices, color) = (&self.renderable.world_vertices, self.renderable.color);
+            gfx.stroke_path(&[vertices[0], vertices[5]], color);
+            gfx.stroke_path(&[vertices[1], vertices[4]], color);
+        }
+
+        for bullet in self.bullets.iter_mut().filter(|x| x.alive) {
+            bullet.render(gfx)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl GameObject for Particles {
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        for particle in self.iter() {
+            let color = particle.color();
+            let location = particle.transform.screen();
+
+            match particle.shape {
+                Shape::Dot => {
+                    let size = particle.radius();
+
+                    if size > 0.05 {
+                        gfx.fill_circle(&Circle::new(location, size), color);
+                    }
+                },
+                Shape::Line(half) => gfx.stroke_path(&[location - half, location + half], color),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -6,6 +6,7 @@ use quicksilver::{
 use rand::Rng;
 
 use super::game_object::GameObject;
+use super::util::rng::GameRng;
 use crate::randf;
 use crate::rand;
 use crate::v;
@@ -15,16 +16,16 @@ pub struct StarField {
 }
 
 impl StarField {
-    pub fn new(window_size: &Vector) -> Self {
+    pub fn new(window_size: &Vector, rng: &mut GameRng) -> Self {
         Self {
-            stars: StarField::generate_stars(window_size)
+            stars: StarField::generate_stars(window_size, rng)
         }
     }
 
-    fn generate_stars(window_size: &Vector) -> Vec<Vector> {
-        let num_stars = rand!(45, 90);
+    fn generate_stars(window_size: &Vector, rng: &mut GameRng) -> Vec<Vector> {
+        let num_stars = rand!(rng, 45, 90);
 
-        (0..num_stars).map(|_| v!(randf!(0.0, window_size.x), randf!(0.0, window_size.y))).collect()
+        (0..num_stars).map(|_| v!(randf!(rng, 0.0, window_size.x), randf!(rng, 0.0, window_size.y))).collect()
     }
 }
 
@@ -33,10 +34,10 @@ impl GameObject for StarField {
         let mut is_distant = false;
         for s in &self.stars {
             if is_distant {
-                let circle = Circle::new(s.clone(), 0.5);
+                let circle = Circle::new(*s, 0.5);
                 gfx.fill_circle(&circle, Color::from_rgba(200, 200, 200, 0.8));
             } else {
-                let circle = Circle::new(s.clone(), 0.8);
+                let circle = Circle::new(*s, 0.8);
                 gfx.fill_circle(&circle, Color::from_rgba(255, 255, 255, 1.0));
             }
 
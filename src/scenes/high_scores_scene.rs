@@ -0,0 +1,88 @@
+use quicksilver::{
+    geom::Vector,
+    graphics::{Color, FontRenderer},
+    Graphics, Input, Result,
+};
+
+use super::scene::{Scene, SceneId, Transition};
+use crate::controls::action::Action;
+use super::game_objects::game_object::GameObject;
+use super::game_objects::menu::centered;
+use super::game_objects::star_field::StarField;
+use crate::persistence::high_scores::{HighScoreTable, MAX_ENTRIES};
+use crate::util::rng::GameRng;
+
+const TITLE_SIZE: f32 = 48.0;
+const TEXT_SIZE: f32 = 24.0;
+const ROW_SPACING: f32 = 36.0;
+
+pub struct HighScoresScene {
+    window_size: Vector,
+    high_scores: HighScoreTable,
+    star_field: StarField,
+    font_title: FontRenderer,
+    font_text: FontRenderer,
+    transition: Option<Transition>,
+}
+
+impl HighScoresScene {
+    pub fn new(window_size: &Vector, high_scores: HighScoreTable, font_title: FontRenderer, font_text: FontRenderer) -> Self {
+        Self {
+            window_size: *window_size,
+            high_scores,
+            star_field: StarField::new(window_size, &mut GameRng::new(GameRng::random_seed())),
+            font_title,
+            font_text,
+            transition: None,
+        }
+    }
+}
+
+impl Scene for HighScoresScene {
+    fn update(&mut self, _input: &mut Input) { }
+
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        gfx.clear(Color::BLACK);
+
+        self.star_field.render(gfx)?;
+
+        let center_x = self.window_size.x / 2.0;
+        let top = self.window_size.y / 5.0;
+
+        let title = "HIGH SCORES";
+        self.font_title.draw(gfx, title, Color::WHITE, centered(title, TITLE_SIZE, center_x, top))?;
+
+        let entries = self.high_scores.entries();
+
+        for i in 0..MAX_ENTRIES {
+            // Every row is the same width so the columns line up
+            let row = match entries.get(i) {
+                Some(entry) => format!("{:>2}. {:<3} {:>8}", i + 1, entry.initials, entry.score),
+                None => format!("{:>2}. {:<3} {:>8}", i + 1, "---", "-"),
+            };
+
+            let y = top + 70.0 + i as f32 * ROW_SPACING;
+            self.font_text.draw(gfx, &row, Color::WHITE, centered(&row, TEXT_SIZE, center_x, y))?;
+        }
+
+        let hint = "PRESS ENTER TO RETURN";
+        let y = top + 90.0 + MAX_ENTRIES as f32 * ROW_SPACING;
+        self.font_text.draw(gfx, hint, Color::from_rgba(255, 255, 255, 0.6), centered(hint, TEXT_SIZE, center_x, y))?;
+
+        Ok(())
+    }
+
+    fn action_up(&mut self, action: Action) {
+        if let Action::Confirm | Action::Cancel = action {
+            self.transition = Some(Transition::Reset(SceneId::Title));
+        }
+    }
+
+    fn should_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
+    }
+}
@@ -1,5 +1,10 @@
 pub mod scene;
 pub mod asteroids_scene;
+pub mod title_scene;
+pub mod pause_scene;
+pub mod high_scores_scene;
+pub mod enter_initials_scene;
+pub mod controls_scene;
 pub mod game_objects;
 
 use super::util;
\ No newline at end of file
@@ -0,0 +1,80 @@
+use quicksilver::{
+    geom::{Rectangle, Vector},
+    graphics::{Color, FontRenderer},
+    Graphics, Input, Result,
+};
+
+use super::scene::{Scene, SceneId, Transition};
+use crate::controls::action::Action;
+use super::game_objects::game_object::GameObject;
+use super::game_objects::menu::{centered, Menu, MenuItem};
+
+const TITLE_SIZE: f32 = 48.0;
+const MENU_SIZE: f32 = 24.0;
+
+/// Overlay pushed on top of the game.  Since only the top scene is updated,
+/// the game underneath is frozen until this scene is popped.
+pub struct PauseScene {
+    window_size: Vector,
+    menu: Menu,
+    font_title: FontRenderer,
+    transition: Option<Transition>,
+}
+
+impl PauseScene {
+    pub fn new(window_size: &Vector, font_title: FontRenderer, font_menu: FontRenderer) -> Self {
+        let items = vec![
+            MenuItem::new("RESUME", Some(Transition::Pop)),
+            MenuItem::new("RESTART", Some(Transition::Reset(SceneId::Asteroids))),
+            MenuItem::new("QUIT TO TITLE", Some(Transition::Reset(SceneId::Title))),
+        ];
+
+        Self {
+            window_size: *window_size,
+            menu: Menu::new(items, font_menu, MENU_SIZE, window_size.x / 2.0, window_size.y / 2.0),
+            font_title,
+            transition: None,
+        }
+    }
+}
+
+impl Scene for PauseScene {
+    fn update(&mut self, _input: &mut Input) { }
+
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        // Dim the frozen playfield underneath
+        let overlay = Rectangle::new(Vector::ZERO, self.window_size);
+        gfx.fill_rect(&overlay, Color::from_rgba(0, 0, 0, 0.6));
+
+        let title = "PAUSED";
+        let location = centered(title, TITLE_SIZE, self.window_size.x / 2.0, self.window_size.y / 3.0);
+        self.font_title.draw(gfx, title, Color::WHITE, location)?;
+
+        self.menu.render(gfx)?;
+
+        Ok(())
+    }
+
+    fn action_up(&mut self, action: Action) {
+        let transition = match action {
+            Action::Pause | Action::Cancel => Some(Transition::Pop),
+            _ => self.menu.action(action),
+        };
+
+        if transition.is_some() {
+            self.transition = transition;
+        }
+    }
+
+    fn is_overlay(&self) -> bool {
+        true
+    }
+
+    fn should_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
+    }
+}
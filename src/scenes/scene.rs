@@ -3,28 +3,65 @@ use quicksilver::{
     Graphics, Input, Result,
 };
 
-#[derive(Clone)]
+use crate::controls::action::Action;
+
+/// Identifies a scene that can be created by a transition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SceneId {
+    Title,
+    Asteroids,
+    Pause,
+    HighScores,
+    EnterInitials(i64),
+    Controls,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Transition {
-    Reset
+    /// Place a new scene on top of the current one
+    Push(SceneId),
+    /// Remove the current scene, returning to the one below it
+    Pop,
+    /// Swap the current scene for a new one
+    Replace(SceneId),
+    /// Throw away the whole scene stack and start over with a new scene
+    Reset(SceneId),
+    /// Exit the game
+    Quit,
 }
 
 pub trait Scene {
     fn update(&mut self, input: &mut Input);
     fn render(&mut self, gfx: &mut Graphics) -> Result<()>;
 
-    fn key_down(&mut self, key: Key) {
-        println!("Key Down: {:?}", key);
+    /// Called every tick for each action whose key is held down.
+    fn action_down(&mut self, _action: Action) { }
+
+    /// Called when a key bound to the action is released.
+    fn action_up(&mut self, _action: Action) { }
+
+    /// Sees every released key before it is turned into actions.  Only
+    /// needed by scenes that care about the key itself, like rebinding.
+    /// Return true to swallow the key.
+    fn key_up(&mut self, _key: Key) -> bool {
+        false
     }
 
-    fn key_up(&mut self, key: Key) {
-        println!("Key Up: {:?}", key);
+    /// Called when the window or browser tab stops receiving input.
+    fn focus_lost(&mut self) { }
+
+    /// Overlays are drawn on top of the scene below them rather than
+    /// replacing it.  Only the top scene is ever updated.
+    fn is_overlay(&self) -> bool {
+        false
     }
 
     fn should_transition(&self) -> bool {
         false
     }
 
-    fn get_transition(&self) -> Option<Transition> {
+    /// Hand the pending transition over to the scene stack, clearing it.
+    fn take_transition(&mut self) -> Option<Transition> {
         None
     }
 }
@@ -0,0 +1,92 @@
+use quicksilver::{
+    geom::Vector,
+    graphics::{Color, FontRenderer},
+    Graphics, Input, Result,
+};
+
+use super::scene::{Scene, SceneId, Transition};
+use crate::controls::action::Action;
+use crate::sim::objects::asteroids::Asteroid;
+use super::game_objects::game_object::GameObject;
+use super::game_objects::menu::{centered, Menu, MenuItem};
+use super::game_objects::star_field::StarField;
+use crate::config::game_config::GameConfig;
+use crate::sim::waves;
+use crate::util::rng::GameRng;
+
+const NUM_ASTEROIDS: usize = 6;
+const LOGO_SIZE: f32 = 96.0;
+const MENU_SIZE: f32 = 24.0;
+
+pub struct TitleScene {
+    window_size: Vector,
+    asteroids: Vec<Asteroid>,
+    star_field: StarField,
+    menu: Menu,
+    font_logo: FontRenderer,
+    transition: Option<Transition>,
+}
+
+impl TitleScene {
+    pub fn new(window_size: &Vector, config: &GameConfig, font_logo: FontRenderer, font_menu: FontRenderer) -> Self {
+        // The background is purely cosmetic so it doesn't need a reproducible seed
+        let mut rng = GameRng::new(GameRng::random_seed());
+
+        let items = vec![
+            MenuItem::new("START", Some(Transition::Replace(SceneId::Asteroids))),
+            MenuItem::new("HIGH SCORES", Some(Transition::Push(SceneId::HighScores))),
+            MenuItem::new("OPTIONS", Some(Transition::Push(SceneId::Controls))),
+            MenuItem::new("QUIT", Some(Transition::Quit)),
+        ];
+
+        Self {
+            window_size: *window_size,
+            asteroids: (0..NUM_ASTEROIDS).map(|_| Asteroid::new(window_size, &config.asteroids, true, waves::wave(1).speed, &mut rng)).collect(),
+            star_field: StarField::new(window_size, &mut rng),
+            menu: Menu::new(items, font_menu, MENU_SIZE, window_size.x / 2.0, window_size.y / 2.0 + MENU_SIZE * 2.0),
+            font_logo,
+            transition: None,
+        }
+    }
+}
+
+impl Scene for TitleScene {
+    fn update(&mut self, _input: &mut Input) {
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.update();
+            asteroid.check_bounds();
+        }
+    }
+
+    fn render(&mut self, gfx: &mut Graphics) -> Result<()> {
+        gfx.clear(Color::BLACK);
+
+        self.star_field.render(gfx)?;
+
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.render(gfx)?;
+        }
+
+        let logo = "ASTEROIDS";
+        let location = centered(logo, LOGO_SIZE, self.window_size.x / 2.0, self.window_size.y / 3.0);
+        self.font_logo.draw(gfx, logo, Color::WHITE, location)?;
+
+        self.menu.render(gfx)?;
+
+        Ok(())
+    }
+
+    fn action_up(&mut self, action: Action) {
+        if let Some(transition) = self.menu.action(action) {
+            self.transition = Some(transition);
+        }
+    }
+
+    fn should_transition(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    fn take_transition(&mut self) -> Option<Transition> {
+        self.transition.take()
+    }
+}
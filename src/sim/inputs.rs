@@ -0,0 +1,50 @@
+const ROTATE_LEFT: u8 = 1;
+const ROTATE_RIGHT: u8 = 1 << 1;
+const THRUST: u8 = 1 << 2;
+const FIRE: u8 = 1 << 3;
+const HYPERSPACE: u8 = 1 << 4;
+const FIRE_HELD: u8 = 1 << 5;
+
+/// The set of player controls sampled for a single simulation tick.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Inputs {
+    pub rotate_left: bool,
+    pub rotate_right: bool,
+    pub thrust: bool,
+    /// Fire was pressed and released
+    pub fire: bool,
+    pub hyperspace: bool,
+    /// Fire is being held down, for auto-fire
+    pub fire_held: bool,
+}
+
+impl Inputs {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Pack the inputs into a single byte, one bit per control.
+    pub fn to_bits(&self) -> u8 {
+        let mut bits = 0;
+
+        if self.rotate_left { bits |= ROTATE_LEFT; }
+        if self.rotate_right { bits |= ROTATE_RIGHT; }
+        if self.thrust { bits |= THRUST; }
+        if self.fire { bits |= FIRE; }
+        if self.hyperspace { bits |= HYPERSPACE; }
+        if self.fire_held { bits |= FIRE_HELD; }
+
+        bits
+    }
+
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            rotate_left: bits & ROTATE_LEFT != 0,
+            rotate_right: bits & ROTATE_RIGHT != 0,
+            thrust: bits & THRUST != 0,
+            fire: bits & FIRE != 0,
+            hyperspace: bits & HYPERSPACE != 0,
+            fire_held: bits & FIRE_HELD != 0,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+pub mod inputs;
+pub mod objects;
+pub mod replay;
+pub mod simulation;
+pub mod waves;
+
+use crate::util;
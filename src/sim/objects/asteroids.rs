@@ -0,0 +1,319 @@
+use quicksilver::{
+    graphics::Color,
+    geom::Vector
+};
+use rand::Rng;
+
+use super::util::collision;
+use super::util::math::{self, VectorMath};
+use super::util::pool::Poolable;
+use super::util::rng::GameRng;
+use super::components::{Collider, Renderable, Transform, Velocity, Wrap};
+use super::particles::{Emitter, Particles};
+use super::systems;
+use crate::config::game_config::AsteroidConfig;
+use crate::randf;
+use crate::rand;
+use crate::v;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Sizes {
+    Small,
+    Medium,
+    Large,
+}
+
+impl Sizes {
+    /// Relative mass for bouncing off each other.  Each size has half the
+    /// radius of the one above, so a quarter of the area.
+    pub fn mass(&self) -> f32 {
+        match self {
+            Sizes::Small => 1.0,
+            Sizes::Medium => 4.0,
+            Sizes::Large => 16.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Asteroid {
+    pub alive: bool,
+    pub size: Sizes,
+    pub transform: Transform,
+    pub velocity: Velocity,
+    pub collider: Collider,
+    pub renderable: Renderable,
+    pub wrap: Wrap,
+    // Hit radius of a large asteroid, which the smaller sizes are scaled from
+    large_radius: f32,
+}
+
+impl Asteroid {
+    /// `speed` is the range of each velocity component, in pixels per tick.
+    pub fn new(window_size: &Vector, config: &AsteroidConfig, alive: bool, speed: (f32, f32), rng: &mut GameRng) -> Self {
+        let object_vertices: Vec<Vector> = Asteroid::generate_vertices(rng).iter()
+                                                                            .map(|x| x.multiply(88.0))
+                                                                            .collect();
+
+        let spin = Asteroid::get_random_degrees(rng);
+        let location = Asteroid::get_random_location(window_size, rng);
+        let velocity = Velocity::spinning(Asteroid::get_random_velocity(speed, rng), spin);
+        let transform = Transform::new(location, window_size.divide(2.0));
+
+        // Placed straight away so collisions on the tick it spawns see it where it is
+        let mut renderable = Renderable::new(object_vertices, Color::from_rgba(237, 187, 153, 1.0));
+        systems::place(&mut renderable, &transform);
+
+        Self {
+            alive,
+            size: Sizes::Large,
+            transform,
+            velocity,
+            collider: Collider::new(config.hit_radius, &renderable.object_vertices),
+            renderable,
+            wrap: Wrap::Around,
+            large_radius: config.hit_radius,
+        }
+    }
+
+    /// A dead asteroid with no shape yet, for growing a pool.  Spawning it
+    /// fills in the rest.
+    pub fn empty(window_size: &Vector, config: &AsteroidConfig) -> Self {
+        Self {
+            alive: false,
+            size: Sizes::Large,
+            transform: Transform::centred(window_size),
+            velocity: Velocity::new(Vector::ZERO),
+            collider: Collider::new(config.hit_radius, &[]),
+            renderable: Renderable::new(vec![], Color::from_rgba(237, 187, 153, 1.0)),
+            wrap: Wrap::Around,
+            large_radius: config.hit_radius,
+        }
+    }
+
+    fn generate_vertices(rng: &mut GameRng) -> Vec<Vector> {
+        // Randomly generate asteroid
+        let mut vertices = vec![];
+        let num_vertices = randf!(rng, 8, 21);
+        let degree_interval = 360.0 / num_vertices;
+        let is_smooth = rand!(rng, 0, 3);  // 1 in 3 chance the asteroid is smooth (more round)
+
+        for i in 0..(num_vertices as i32) {
+            let deg = degree_interval * i as f32;
+            let mag = if is_smooth == 0 { randf!(rng, 0.3, 0.4) } else { randf!(rng, 0.2, 0.45) };
+
+            let v = v!(mag, 0.0).rotate(deg);
+
+            vertices.push(v);
+        }
+
+        vertices
+    }
+
+    fn get_random_location(window_size: &Vector, rng: &mut GameRng) -> Vector {
+        let lx = randf!(rng) % window_size.x / 2.0;
+        let ly = randf!(rng) % window_size.y / 2.0;
+
+        v!(lx, ly)
+    }
+
+    fn get_random_sign(rng: &mut GameRng) -> f32 {
+        2.0 * randf!(rng, 2) - 1.0
+    }
+
+    fn get_random_velocity(speed: (f32, f32), rng: &mut GameRng) -> Vector {
+        // Speeds are picked in thousandths of a pixel
+        let (min, max) = ((speed.0 * 1000.0) as i32, (speed.1 * 1000.0) as i32);
+
+        let vx = randf!(rng, min, max) / 1000.0 * Asteroid::get_random_sign(rng);
+        let vy = randf!(rng, min, max) / 1000.0 * Asteroid::get_random_sign(rng);
+
+        v!(vx, vy)
+    }
+
+    fn get_random_degrees(rng: &mut GameRng) -> f32 {
+        Asteroid::get_random_sign(rng) * (randf!(rng, 100) + 1000.0) / 1000.0
+    }
+
+    pub fn is_dead(&self) -> bool {
+        !self.alive
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    fn playfield(&self) -> Vector {
+        self.transform.playfield()
+    }
+
+    pub fn check_bounds(&mut self) {
+        let playfield = self.playfield();
+
+        // Keep the outline with the asteroid when it jumps to the other side
+        if let Some(shift) = systems::wrap(&mut self.transform, self.wrap, playfield) {
+            self.renderable.shift(shift);
+        }
+    }
+
+    /// Move the asteroid without disturbing anything else, e.g. to push it
+    /// out of another one.
+    pub fn nudge(&mut self, offset: Vector) {
+        self.transform.location += offset;
+        self.renderable.shift(offset);
+        self.check_bounds();
+    }
+
+    pub fn check_collision(&self, vec: Vector, radius: f32) -> bool {
+        if !self.alive { return false }
+
+        math::wrapped_distance(self.transform.location, vec, self.playfield()) < self.collider.hit_radius + radius
+    }
+
+    // Where `vec` appears closest to the asteroid, which may be across an edge
+    fn nearest_image(&self, vec: Vector) -> Vector {
+        self.transform.location - math::wrapped_delta(self.transform.location, vec, self.playfield())
+    }
+
+    /// How far from its location anything can be and still hit the asteroid.
+    pub fn reach(&self) -> f32 {
+        self.collider.bounding_radius.max(self.collider.hit_radius)
+    }
+
+    /// The outline as it was last drawn, in the same coordinates as `location`.
+    fn outline(&self) -> Vec<Vector> {
+        self.renderable.world_vertices.iter().map(|x| *x - self.transform.translation).collect()
+    }
+
+    // Broad phase: could anything within `radius` of `vec` touch the outline?
+    fn might_touch(&self, vec: Vector, radius: f32) -> bool {
+        self.alive && math::wrapped_distance(self.transform.location, vec, self.playfield()) < self.collider.bounding_radius + radius
+    }
+
+    /// Whether a point, such as a bullet, is inside the asteroid's outline.
+    pub fn check_point_collision(&self, point: Vector) -> bool {
+        self.might_touch(point, 0.0) && collision::point_in_polygon(self.nearest_image(point), &self.outline())
+    }
+
+    /// Whether a polygon overlaps the asteroid's outline.  `location` and
+    /// `radius` give a circle around the polygon for the broad phase.
+    pub fn check_polygon_collision(&self, location: Vector, radius: f32, vertices: &[Vector]) -> bool {
+        if !self.might_touch(location, radius) {
+            return false;
+        }
+
+        let shift = self.nearest_image(location) - location;
+        let vertices: Vec<Vector> = vertices.iter().map(|x| *x + shift).collect();
+
+        collision::polygons_intersect(&vertices, &self.outline())
+    }
+
+    pub fn handle_collision(&mut self, rng: &mut GameRng, particles: &mut Particles) {
+        self.alive = false;
+
+        let size = match self.size {
+            Sizes::Small => 6.0,
+            Sizes::Medium => 12.0,
+            Sizes::Large => 24.0,
+        };
+
+        let count = rand!(rng, 3, 9);
+        Emitter::sparks(size, 12).burst(particles, self.transform.location, count, rng);
+        Emitter::debris(self.renderable.color, 20).shatter(particles, self.transform.location, &self.renderable.object_vertices);
+    }
+
+    pub fn spawn_asteroid(&mut self, location: &Vector, size: &Sizes, speed: (f32, f32), rng: &mut GameRng) {
+        self.transform.location = *location;
+        self.alive = true;
+        self.velocity.linear = Asteroid::get_random_velocity(speed, rng);
+        self.velocity.spin = Asteroid::get_random_degrees(rng);
+
+        self.shrink_asteroid(size, rng);
+    }
+
+    pub fn shrink_asteroid(&mut self, size: &Sizes, rng: &mut GameRng) {
+        let object_vertices = Asteroid::generate_vertices(rng);
+
+        // converts verts from obj space to world space and translate world space to screen space
+        let object_vertices: Vec<Vector> = object_vertices.iter().map(|x| x.multiply(88.0)).collect();
+
+        self.size = match size {
+            Sizes::Large => {
+                self.renderable.object_vertices = object_vertices.iter().map(|x| x.divide(2.0)).collect();
+                self.collider.hit_radius = self.large_radius / 2.0;
+                Sizes::Medium
+            },
+            Sizes::Medium => {
+                self.renderable.object_vertices = object_vertices.iter().map(|x| x.divide(4.0)).collect();
+                self.collider.hit_radius = self.large_radius / 4.0;
+                Sizes::Small
+            },
+            Sizes::Small => {
+                self.renderable.object_vertices = object_vertices.iter().map(|x| x.divide(8.0)).collect();
+                self.collider.hit_radius = self.large_radius / 8.0;
+                Sizes::Small
+            }
+        };
+
+        self.collider.fit(&self.renderable.object_vertices);
+    }
+
+    pub fn update(&mut self) {
+        // Move asteroid's location based on current velocity vector
+        systems::integrate(&mut self.transform, &mut self.velocity);
+
+        // Translate object vertices to world vertices for rendering
+        systems::place(&mut self.renderable, &self.transform);
+
+        // Rotate asteroid for next render/update cycle
+        systems::spin(&mut self.renderable, &self.velocity);
+    }
+}
+
+impl Poolable for Asteroid {
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::game_config::GameConfig;
+
+    #[test]
+    fn new_asteroids_are_placed_at_their_location() {
+        let window_size = v!(1024.0, 768.0);
+        let asteroid = Asteroid::new(&window_size, &GameConfig::default().asteroids, true, (1.0, 2.0), &mut GameRng::new(7));
+
+        let location = asteroid.transform.location;
+        let outline = asteroid.outline();
+        let centre = outline.iter().fold(Vector::ZERO, |sum, x| sum + *x).divide(outline.len() as f32);
+
+        assert_ne!(location, Vector::ZERO);
+        assert!(outline.iter().zip(asteroid.renderable.object_vertices.iter()).all(|(x, v)| (*x - *v - location).magnitude() < 0.01));
+        assert!((centre - location).magnitude() < asteroid.collider.bounding_radius / 2.0);
+    }
+
+    #[test]
+    fn points_must_be_inside_the_outline() {
+        let window_size = v!(1024.0, 768.0);
+        let mut asteroid = Asteroid::new(&window_size, &GameConfig::default().asteroids, true, (1.0, 2.0), &mut GameRng::new(1));
+
+        // A square with a slot cut into its right side
+        asteroid.transform.location = Vector::ZERO;
+        asteroid.renderable.object_vertices = vec![
+            v!(-40.0, -40.0), v!(40.0, -40.0), v!(40.0, -10.0), v!(0.0, -10.0),
+            v!(0.0, 10.0), v!(40.0, 10.0), v!(40.0, 40.0), v!(-40.0, 40.0),
+        ];
+        asteroid.collider.fit(&asteroid.renderable.object_vertices);
+        systems::place(&mut asteroid.renderable, &asteroid.transform);
+
+        let in_slot = v!(20.0, 0.0);
+        assert!(in_slot.magnitude() < asteroid.collider.bounding_radius);
+        assert!(!asteroid.check_point_collision(in_slot));
+
+        assert!(asteroid.check_point_collision(v!(-20.0, 0.0)));
+        assert!(!asteroid.check_point_collision(v!(100.0, 0.0)));
+    }
+}
@@ -0,0 +1,72 @@
+use quicksilver::geom::Vector;
+
+use super::util::pool::Poolable;
+use super::components::{Lifetime, Transform, Velocity, Wrap};
+use super::systems;
+
+/// Bullets are in screen space, so their transform has no translation.
+#[derive(Debug, Clone)]
+pub struct Bullet {
+    pub transform: Transform,
+    pub velocity: Velocity,
+    pub lifetime: Lifetime,
+    pub wrap: Wrap,
+    pub alive: bool,
+}
+
+impl Bullet {
+    pub fn new() -> Self {
+        Self {
+            transform: Transform::new(Vector::ZERO, Vector::ZERO),
+            velocity: Velocity::new(Vector::ZERO),
+            lifetime: Lifetime::new(0),
+            wrap: Wrap::Screen,
+            alive: false,
+        }
+    }
+
+    /// Bring the bullet to life, expiring after `lifetime` ticks.
+    pub fn fire(&mut self, location: Vector, velocity: Vector, lifetime: i32) {
+        self.alive = true;
+        self.transform.location = location;
+        self.velocity.linear = velocity;
+        self.lifetime = Lifetime::new(lifetime);
+    }
+
+    pub fn handle_collision(&mut self) {
+        self.alive = false;
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    /// Wrap around the edges of a playfield of the given size.
+    pub fn wrap(&mut self, size: Vector) {
+        systems::wrap(&mut self.transform, self.wrap, size);
+    }
+
+    pub fn update(&mut self) {
+        if !self.alive {
+            return;
+        }
+
+        systems::integrate(&mut self.transform, &mut self.velocity);
+
+        if !systems::age(&mut self.lifetime) {
+            self.alive = false;
+        }
+    }
+}
+
+impl Default for Bullet {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Poolable for Bullet {
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+}
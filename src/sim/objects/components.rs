@@ -0,0 +1,126 @@
+use quicksilver::{
+    graphics::Color,
+    geom::Vector,
+};
+
+use super::util::collision;
+use super::util::math::VectorMath;
+
+// Building blocks game objects are made of.  Each is plain data; the
+// functions in `systems` do the work, so a new kind of object only needs to
+// pick the components it wants and call the systems that apply to them.
+
+/// Where something is.  `location` is relative to the centre of the
+/// playfield and `translation` takes it to the screen, which for most things
+/// is half the playfield.  Bullets live in screen space and have none.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub location: Vector,
+    pub translation: Vector,
+}
+
+impl Transform {
+    pub fn new(location: Vector, translation: Vector) -> Self {
+        Self { location, translation }
+    }
+
+    /// Centred at the origin of a playfield of the given size.
+    pub fn centred(window_size: &Vector) -> Self {
+        Self::new(Vector::ZERO, window_size.divide(2.0))
+    }
+
+    pub fn screen(&self) -> Vector {
+        self.location + self.translation
+    }
+
+    pub fn playfield(&self) -> Vector {
+        self.translation.multiply(2.0)
+    }
+}
+
+/// How something moves each tick.  `spin` turns its outline, in degrees.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Velocity {
+    pub linear: Vector,
+    pub spin: f32,
+    pub max_speed: Option<f32>,
+}
+
+impl Velocity {
+    pub fn new(linear: Vector) -> Self {
+        Self { linear, spin: 0.0, max_speed: None }
+    }
+
+    pub fn spinning(linear: Vector, spin: f32) -> Self {
+        Self { spin, ..Self::new(linear) }
+    }
+
+    pub fn limited(max_speed: f32) -> Self {
+        Self { max_speed: Some(max_speed), ..Self::new(Vector::ZERO) }
+    }
+}
+
+/// What happens at the edges of the playfield.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Wrap {
+    /// Jump straight across to the opposite edge
+    Around,
+    /// Jump between top and bottom, but leave for good through the sides
+    Vertical,
+    /// Come back in the other side keeping any overshoot, for things in
+    /// screen space running from zero to the playfield size
+    Screen,
+}
+
+/// What something can be hit by.  `hit_radius` is the circle used against
+/// other circles; `bounding_radius` contains the whole outline, for ruling
+/// out polygon tests cheaply.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Collider {
+    pub hit_radius: f32,
+    pub bounding_radius: f32,
+}
+
+impl Collider {
+    pub fn new(hit_radius: f32, outline: &[Vector]) -> Self {
+        Self { hit_radius, bounding_radius: collision::bounding_radius(outline) }
+    }
+
+    /// Fit the bounding circle to a new outline.
+    pub fn fit(&mut self, outline: &[Vector]) {
+        self.bounding_radius = collision::bounding_radius(outline);
+    }
+}
+
+/// An outline drawn in a single colour.  `object_vertices` are around the
+/// object's own origin; `world_vertices` are where they were last placed on
+/// screen.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Renderable {
+    pub object_vertices: Vec<Vector>,
+    pub world_vertices: Vec<Vector>,
+    pub color: Color,
+}
+
+impl Renderable {
+    pub fn new(object_vertices: Vec<Vector>, color: Color) -> Self {
+        Self { object_vertices, world_vertices: vec![], color }
+    }
+
+    /// Move the placed outline along with its object.
+    pub fn shift(&mut self, offset: Vector) {
+        self.world_vertices.iter_mut().for_each(|x| *x += offset);
+    }
+}
+
+/// Ticks until something expires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lifetime {
+    pub ticks_left: i32,
+}
+
+impl Lifetime {
+    pub fn new(ticks: i32) -> Self {
+        Self { ticks_left: ticks }
+    }
+}
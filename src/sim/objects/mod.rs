@@ -0,0 +1,9 @@
+pub mod asteroids;
+pub mod bullet;
+pub mod components;
+pub mod particles;
+pub mod player;
+pub mod saucer;
+pub mod systems;
+
+use super::util;
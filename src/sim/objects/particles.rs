@@ -0,0 +1,294 @@
+use quicksilver::{
+    graphics::Color,
+    geom::Vector
+};
+use rand::Rng;
+
+use super::util::math::VectorMath;
+use super::util::pool::{Handle, Pool, Poolable};
+use super::util::rng::GameRng;
+use super::components::{Lifetime, Transform, Velocity};
+use super::systems;
+use crate::randf;
+use crate::v;
+
+/// What a particle looks like.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    /// A filled circle
+    Dot,
+    /// A line through the particle's location, given by half of it.  Turns
+    /// with the particle's spin.
+    Line(Vector),
+}
+
+/// A colour that changes over a particle's life, from `start` when it's
+/// emitted to `end` as it expires.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Gradient {
+    pub start: Color,
+    pub end: Color,
+}
+
+impl Gradient {
+    pub fn new(start: Color, end: Color) -> Self {
+        Self { start, end }
+    }
+
+    /// The same colour, fading out.
+    pub fn fade(color: Color) -> Self {
+        Self::new(color, Color { a: 0.0, ..color })
+    }
+
+    /// The colour `t` of the way through, from 0.0 to 1.0.
+    pub fn at(&self, t: f32) -> Color {
+        Color {
+            r: lerp(self.start.r, self.end.r, t),
+            g: lerp(self.start.g, self.end.g, t),
+            b: lerp(self.start.b, self.end.b, t),
+            a: lerp(self.start.a, self.end.a, t),
+        }
+    }
+}
+
+fn lerp(start: f32, end: f32, t: f32) -> f32 {
+    start + (end - start) * t
+}
+
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub transform: Transform,
+    pub velocity: Velocity,
+    pub lifetime: Lifetime,
+    pub shape: Shape,
+    alive: bool,
+    // Ticks it was emitted with, for how far through its life it is
+    ticks: i32,
+    // Radius of a dot at the start and end of its life
+    size: (f32, f32),
+    gradient: Gradient,
+}
+
+impl Particle {
+    pub fn new() -> Self {
+        Self {
+            transform: Transform::new(Vector::ZERO, Vector::ZERO),
+            velocity: Velocity::new(Vector::ZERO),
+            lifetime: Lifetime::new(0),
+            shape: Shape::Dot,
+            alive: false,
+            ticks: 1,
+            size: (0.0, 0.0),
+            gradient: Gradient::fade(Color::WHITE),
+        }
+    }
+
+    // How far through its life it is, from 0.0 to 1.0
+    fn age(&self) -> f32 {
+        1.0 - self.lifetime.ticks_left as f32 / self.ticks as f32
+    }
+
+    pub fn color(&self) -> Color {
+        self.gradient.at(self.age())
+    }
+
+    /// The radius of a dot at this point in its life.
+    pub fn radius(&self) -> f32 {
+        lerp(self.size.0, self.size.1, self.age())
+    }
+}
+
+impl Default for Particle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Poolable for Particle {
+    fn is_alive(&self) -> bool {
+        self.alive
+    }
+}
+
+/// Describes the particles something gives off.  The same emitter can
+/// scatter a burst of dots, break an outline into pieces or trail single
+/// particles behind something moving.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Emitter {
+    pub lifetime: i32,
+    pub speed: f32,
+    pub size: (f32, f32),
+    pub gradient: Gradient,
+}
+
+impl Emitter {
+    /// Glowing dots flying out from an explosion, shrinking as they go.
+    pub fn sparks(size: f32, lifetime: i32) -> Self {
+        Self {
+            lifetime,
+            speed: 2.0,
+            size: (size, 0.0),
+            gradient: Gradient::new(Color::from_rgba(248, 196, 113, 1.0), Color::from_rgba(231, 76, 60, 0.5)),
+        }
+    }
+
+    /// Pieces of a broken outline drifting apart.
+    pub fn debris(color: Color, lifetime: i32) -> Self {
+        Self {
+            lifetime,
+            speed: 0.75,
+            size: (0.0, 0.0),
+            gradient: Gradient::fade(color),
+        }
+    }
+
+    /// Puffs left behind by the ship's engine.
+    pub fn exhaust() -> Self {
+        Self {
+            lifetime: 10,
+            speed: 0.0,
+            size: (4.0, 0.0),
+            gradient: Gradient::fade(Color::from_rgba(127, 179, 213, 1.0)),
+        }
+    }
+
+    /// A single particle.  The handle is only good until the particle expires
+    /// or is pushed out to make room.
+    pub fn emit(&self, particles: &mut Particles, shape: Shape, location: Vector, velocity: Velocity) -> Option<Handle> {
+        particles.spawn(Particle {
+            transform: Transform::new(location, particles.translation),
+            velocity,
+            lifetime: Lifetime::new(self.lifetime),
+            shape,
+            alive: true,
+            ticks: self.lifetime,
+            size: self.size,
+            gradient: self.gradient,
+        })
+    }
+
+    /// `count` dots spread evenly around `location`, each knocked a little
+    /// off course.
+    pub fn burst(&self, particles: &mut Particles, location: Vector, count: i32, rng: &mut GameRng) {
+        for d in 0..count {
+            let degrees = d as f32 * (360.0 / count as f32) + randf!(rng, -10, 14);
+            let velocity = v!(self.speed, 0.0).rotate(degrees);
+
+            self.emit(particles, Shape::Dot, location, Velocity::new(velocity));
+        }
+    }
+
+    /// Break an outline, given around `location`, into one line per edge,
+    /// each drifting away from the middle and tumbling.
+    pub fn shatter(&self, particles: &mut Particles, location: Vector, outline: &[Vector]) {
+        let next = outline.iter().cycle().skip(1);
+
+        for (i, (a, b)) in outline.iter().zip(next).enumerate() {
+            // Closed outlines repeat their first vertex
+            if a == b {
+                continue;
+            }
+
+            let middle = (*a + *b).divide(2.0);
+            let velocity = if middle == Vector::ZERO { Vector::ZERO } else { middle.normalize().multiply(self.speed) };
+            let spin = if i % 2 == 0 { 3.0 } else { -3.0 };
+
+            self.emit(particles, Shape::Line((*b - *a).divide(2.0)), location + middle, Velocity::spinning(velocity, spin));
+        }
+    }
+}
+
+/// Every particle in the game, sharing one budget.  Once it's used up, new
+/// particles take the place of the ones closest to expiring, whose handles
+/// then stop working.
+pub struct Particles {
+    pool: Pool<Particle>,
+    translation: Vector,
+}
+
+impl Particles {
+    pub fn new(window_size: &Vector, budget: usize) -> Self {
+        Self {
+            pool: Pool::fixed(vec![Particle::new(); budget]),
+            translation: window_size.divide(2.0),
+        }
+    }
+
+    fn spawn(&mut self, particle: Particle) -> Option<Handle> {
+        let (handle, slot) = match self.pool.acquire() {
+            Some(acquired) => acquired,
+            None => {
+                let oldest = self.pool.alive().min_by_key(|(_, x)| x.lifetime.ticks_left)?.0;
+                self.pool.reclaim(oldest)?
+            },
+        };
+
+        *slot = particle;
+        Some(handle)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.pool.iter().filter(|x| x.alive)
+    }
+
+    /// Put particles out early, e.g. a trail whose source has vanished.
+    /// Ones that have already gone are skipped.
+    pub fn kill(&mut self, handles: &[Handle]) {
+        for handle in handles {
+            if let Some(particle) = self.pool.get_mut(*handle) {
+                particle.alive = false;
+            }
+        }
+    }
+
+    pub fn update(&mut self) {
+        for particle in self.pool.iter_mut().filter(|x| x.alive) {
+            systems::integrate(&mut particle.transform, &mut particle.velocity);
+
+            if let Shape::Line(half) = particle.shape {
+                particle.shape = Shape::Line(half.rotate(particle.velocity.spin));
+            }
+
+            if !systems::age(&mut particle.lifetime) {
+                particle.alive = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn emit(particles: &mut Particles, lifetime: i32) -> Option<Handle> {
+        Emitter { lifetime, ..Emitter::exhaust() }.emit(particles, Shape::Dot, Vector::ZERO, Velocity::new(Vector::ZERO))
+    }
+
+    #[test]
+    fn full_budgets_replace_the_particle_closest_to_expiring() {
+        let mut particles = Particles::new(&v!(1024.0, 768.0), 3);
+        let long = emit(&mut particles, 30).unwrap();
+        let short = emit(&mut particles, 5).unwrap();
+        let medium = emit(&mut particles, 10).unwrap();
+
+        let newest = emit(&mut particles, 20).unwrap();
+
+        assert!(particles.pool.get(short).is_none());
+        assert!(particles.pool.get(long).is_some());
+        assert!(particles.pool.get(medium).is_some());
+        assert_eq!(particles.pool.get(newest).unwrap().lifetime.ticks_left, 20);
+    }
+
+    #[test]
+    fn killed_particles_free_their_slots() {
+        let mut particles = Particles::new(&v!(1024.0, 768.0), 2);
+        let a = emit(&mut particles, 10).unwrap();
+        let b = emit(&mut particles, 10).unwrap();
+
+        particles.kill(&[a]);
+        particles.kill(&[a]);
+
+        assert_eq!(particles.pool.alive_count(), 1);
+        assert!(particles.pool.get(b).unwrap().alive);
+    }
+}
@@ -0,0 +1,303 @@
+use quicksilver::{
+    graphics::Color,
+    geom::Vector
+};
+use rand::Rng;
+
+use super::util::math::VectorMath;
+use super::util::rng::GameRng;
+use super::util::pool::{Handle, Pool};
+use super::components::{Collider, Renderable, Transform, Velocity, Wrap};
+use super::particles::{Emitter, Particles, Shape};
+use super::systems;
+use super::bullet::Bullet;
+use crate::config::game_config::PlayerConfig;
+use crate::rand;
+use crate::v;
+
+// Ticks spent invisible in hyperspace, and before the drive can be used again
+const HYPERSPACE_DURATION: i32 = 20;
+const HYPERSPACE_COOLDOWN: i32 = 90;
+pub const WARP_RADIUS: f32 = 36.0;
+
+// Ticks before a lost ship may return, and how long it's protected once it does
+const RESPAWN_DELAY: i32 = 30;
+const INVULNERABLE_DURATION: i32 = 90;
+const BLINK_RATE: i32 = 4;
+
+pub struct Player {
+    pub config: PlayerConfig,
+    pub lives: i32,
+    pub transform: Transform,
+    pub velocity: Velocity,
+    pub collider: Collider,
+    pub renderable: Renderable,
+    pub wrap: Wrap,
+    pub bullets: Pool<Bullet>,
+    pub warp_out: Vec<(Vector, f32)>,
+    // The trail behind the ship, newest last, so it can vanish with the ship
+    exhaust: Vec<Handle>,
+    hyperspace_ticks: i32,
+    hyperspace_cooldown: i32,
+    respawning: bool,
+    respawn_delay: i32,
+    invulnerable_ticks: i32,
+    fire_cooldown: i32,
+}
+
+impl Player {
+    pub fn new(window_size: &Vector, config: &PlayerConfig) -> Self {
+        let transform = Transform::centred(window_size);
+
+        let object_vertices = vec!(v!(0.0, -18.0), v!(12.0, 12.0), v!(-12.0, 12.0), v!(0.0, -18.0));
+
+        let mut renderable = Renderable::new(object_vertices, Color::from_rgba(255, 255, 255, 1.0));
+        systems::place(&mut renderable, &transform);
+
+        Self {
+            config: *config,
+            lives: 3,
+            transform,
+            velocity: Velocity::limited(config.speed_limit),
+            collider: Collider::new(15.0, &renderable.object_vertices),
+            renderable,
+            wrap: Wrap::Around,
+            bullets: Pool::fixed(vec![Bullet::new(); config.bullets]),
+            warp_out: vec![],
+            exhaust: vec![],
+            hyperspace_ticks: 0,
+            hyperspace_cooldown: 0,
+            // The first ship waits for a clear spawn point like any other
+            respawning: true,
+            respawn_delay: RESPAWN_DELAY,
+            invulnerable_ticks: 0,
+            fire_cooldown: 0,
+        }
+    }
+
+    pub fn apply_force(&mut self, v: Vector) {
+        self.velocity.linear += v;
+    }
+
+    pub fn rotate(&mut self, degrees: f32) {
+        self.renderable.object_vertices = self.renderable.object_vertices.iter()
+                                                                         .map(|x| x.rotate(degrees))
+                                                                         .collect();
+
+        self.collider.fit(&self.renderable.object_vertices);
+    }
+
+    pub fn get_direction(&self) -> Vector {
+        if let Some(direction) = self.renderable.object_vertices.first() {
+            direction.normalize()
+        } else {
+            Vector::ZERO
+        }
+    }
+
+    pub fn apply_thrust(&mut self, particles: &mut Particles) {
+        let direction = self.get_direction();
+        // println!("Ship Direction: {:?}", direction);
+        let thrust = direction.multiply(self.config.thrust);
+        // println!("Applying Thrust: {:?}", thrust);
+        self.apply_force(thrust);
+
+        self.generate_exhaust(particles);
+    }
+
+    pub fn generate_exhaust(&mut self, particles: &mut Particles) {
+        if let Some(head) = self.renderable.object_vertices.first() {
+            let direction = head.normalize();
+
+            let exhaust = *head + self.transform.location - direction.multiply(28.0);
+
+            let emitter = Emitter::exhaust();
+
+            if let Some(handle) = emitter.emit(particles, Shape::Dot, exhaust, Velocity::new(Vector::ZERO)) {
+                self.exhaust.push(handle);
+            }
+
+            // Anything older has expired by now
+            let expired = self.exhaust.len().saturating_sub(emitter.lifetime as usize);
+            self.exhaust.drain(..expired);
+        }
+    }
+
+    fn clear_exhaust(&mut self, particles: &mut Particles) {
+        particles.kill(&self.exhaust);
+        self.exhaust.clear();
+    }
+
+    pub fn check_bounds(&mut self) {
+        let size = self.transform.playfield();
+
+        // Keep the outline with the ship when it jumps to the other side
+        if let Some(shift) = systems::wrap(&mut self.transform, self.wrap, size) {
+            self.renderable.shift(shift);
+        }
+
+        // bullets are in world space
+        self.bullets.iter_mut().filter(|x| x.alive).for_each(|x| x.wrap(size));
+    }
+
+    pub fn handle_collsion(&mut self, rng: &mut GameRng, particles: &mut Particles) {
+        let count = 2 * rand!(rng, 5, 10) - 1;
+        Emitter::sparks(12.0, 16).burst(particles, self.transform.location, count, rng);
+        Emitter::debris(self.renderable.color, 45).shatter(particles, self.transform.location, &self.renderable.object_vertices);
+        self.clear_exhaust(particles);
+
+        self.transform.location = Vector::ZERO;
+        self.velocity.linear = Vector::ZERO;
+        self.hyperspace_ticks = 0;
+        self.lives = if self.lives > 0 { self.lives - 1 } else { 0 };
+
+        if self.is_alive() {
+            self.respawning = true;
+            self.respawn_delay = RESPAWN_DELAY;
+        }
+    }
+
+    /// The ship's outline in the same coordinates as `location`.
+    pub fn hull(&self) -> Vec<Vector> {
+        self.renderable.world_vertices.iter().map(|x| *x - self.transform.translation).collect()
+    }
+
+    /// True while the ship is off the board waiting for its spawn point to clear.
+    pub fn is_respawning(&self) -> bool {
+        self.respawning
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        self.invulnerable_ticks > 0
+    }
+
+    /// Whether anything can currently hit the ship.
+    pub fn is_vulnerable(&self) -> bool {
+        self.is_alive() && !self.respawning && !self.is_invulnerable() && !self.is_in_hyperspace()
+    }
+
+    /// Count down the respawn delay and protection.  The ship only returns
+    /// once the delay is over and `spawn_clear` says nothing is waiting for it.
+    pub fn update_respawn(&mut self, spawn_clear: bool) {
+        if self.invulnerable_ticks > 0 {
+            self.invulnerable_ticks -= 1;
+        }
+
+        if !self.respawning {
+            return;
+        }
+
+        if self.respawn_delay > 0 {
+            self.respawn_delay -= 1;
+        } else if spawn_clear {
+            self.respawning = false;
+            self.invulnerable_ticks = INVULNERABLE_DURATION;
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.lives > 0
+    }
+
+    pub fn is_in_hyperspace(&self) -> bool {
+        self.hyperspace_ticks > 0
+    }
+
+    /// How far the hyperspace drive has recharged, from 0.0 to 1.0.
+    pub fn hyperspace_charge(&self) -> f32 {
+        1.0 - self.hyperspace_cooldown as f32 / HYPERSPACE_COOLDOWN as f32
+    }
+
+    pub fn can_hyperspace(&self) -> bool {
+        self.is_alive() && !self.respawning && !self.is_in_hyperspace() && self.hyperspace_cooldown == 0
+    }
+
+    /// Vanish and head for `destination`.  Returns false if the drive is
+    /// still recharging.
+    pub fn enter_hyperspace(&mut self, destination: Vector, particles: &mut Particles) -> bool {
+        if !self.can_hyperspace() {
+            return false;
+        }
+
+        self.warp_out.push((self.transform.location, 0.0));
+        self.clear_exhaust(particles);
+
+        self.transform.location = destination;
+        self.velocity.linear = Vector::ZERO;
+        self.hyperspace_ticks = HYPERSPACE_DURATION;
+        self.hyperspace_cooldown = HYPERSPACE_COOLDOWN;
+
+        true
+    }
+
+    /// Count down the jump, returning true on the tick the ship reappears.
+    pub fn update_hyperspace(&mut self) -> bool {
+        if self.hyperspace_cooldown > 0 {
+            self.hyperspace_cooldown -= 1;
+        }
+
+        if self.hyperspace_ticks > 0 {
+            self.hyperspace_ticks -= 1;
+            return self.hyperspace_ticks == 0;
+        }
+
+        false
+    }
+
+    /// Whether a shot is allowed: the gun has cycled since the last one and
+    /// there's room on screen for another bullet.
+    pub fn can_shoot(&self) -> bool {
+        let in_flight = self.bullets.alive_count();
+
+        self.fire_cooldown == 0 && in_flight < self.config.max_bullets
+    }
+
+    pub fn shoot_bullet(&mut self) {
+        if !self.can_shoot() {
+            return;
+        }
+
+        let velocity = self.get_direction().multiply(self.config.bullet_speed);
+        let location = *self.renderable.world_vertices.first().unwrap();
+
+        if let Some((_, bullet)) = self.bullets.acquire() {
+            bullet.fire(location, velocity, self.config.bullet_lifetime);
+
+            self.fire_cooldown = self.config.fire_rate;
+        }
+    }
+
+    /// The warp-in ring closing on the destination, while the ship is in hyperspace.
+    pub fn warp_in_radius(&self) -> Option<f32> {
+        if !self.is_in_hyperspace() {
+            return None;
+        }
+
+        Some(WARP_RADIUS * self.hyperspace_ticks as f32 / HYPERSPACE_DURATION as f32)
+    }
+
+    /// Whether the ship should be drawn this tick; it blinks while invulnerable.
+    pub fn is_visible(&self) -> bool {
+        let blink_off = self.is_invulnerable() && (self.invulnerable_ticks / BLINK_RATE) % 2 == 1;
+
+        self.is_alive() && !self.respawning && !self.is_in_hyperspace() && !blink_off
+    }
+
+    pub fn update(&mut self) {
+        systems::integrate(&mut self.transform, &mut self.velocity);
+
+        if self.fire_cooldown > 0 {
+            self.fire_cooldown -= 1;
+        }
+
+        // println!("Ship Location: {:?}", self.location);
+        // println!("Ship Velocity: {:?}", self.velocity);
+
+        systems::place(&mut self.renderable, &self.transform);
+
+        self.bullets.iter_mut().for_each(|x| x.update());
+
+        self.warp_out.iter_mut().for_each(|x| x.1 += 2.0);
+        self.warp_out.retain(|x| x.1 < WARP_RADIUS);
+    }
+}
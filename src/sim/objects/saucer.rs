@@ -0,0 +1,215 @@
+use quicksilver::{
+    graphics::Color,
+    geom::Vector
+};
+use rand::Rng;
+
+use super::util::math::{self, VectorMath};
+use super::util::rng::GameRng;
+use super::util::pool::Pool;
+use super::components::{Collider, Renderable, Transform, Velocity, Wrap};
+use super::particles::{Emitter, Particles};
+use super::systems;
+use super::bullet::Bullet;
+use crate::randf;
+use crate::rand;
+use crate::v;
+
+const NUM_BULLETS: usize = 4;
+const BULLET_SPEED: f32 = 6.0;
+const BULLET_LIFETIME: i32 = 60;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SaucerSize {
+    Small,
+    Large,
+}
+
+impl SaucerSize {
+    fn scale(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 24.0,
+            SaucerSize::Small => 12.0,
+        }
+    }
+
+    fn hit_radius(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 20.0,
+            SaucerSize::Small => 10.0,
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            SaucerSize::Large => 2.0,
+            SaucerSize::Small => 3.0,
+        }
+    }
+
+    // Ticks between shots
+    fn fire_rate(&self) -> i32 {
+        match self {
+            SaucerSize::Large => 45,
+            SaucerSize::Small => 30,
+        }
+    }
+
+    pub fn score(&self) -> i64 {
+        match self {
+            SaucerSize::Large => 200,
+            SaucerSize::Small => 1000,
+        }
+    }
+}
+
+/// Flying saucer enemy.  Crosses the screen horizontally, changing vertical
+/// course now and then, and shoots at the player.  The large saucer fires in
+/// random directions, the small one aims.
+#[derive(Debug, Clone)]
+pub struct Saucer {
+    pub alive: bool,
+    pub size: SaucerSize,
+    pub transform: Transform,
+    pub velocity: Velocity,
+    pub collider: Collider,
+    pub renderable: Renderable,
+    pub wrap: Wrap,
+    pub bullets: Pool<Bullet>,
+    fire_cooldown: i32,
+    course_cooldown: i32,
+}
+
+impl Saucer {
+    pub fn new(window_size: &Vector) -> Self {
+        let object_vertices = Saucer::generate_vertices(SaucerSize::Large);
+
+        Self {
+            alive: false,
+            size: SaucerSize::Large,
+            transform: Transform::centred(window_size),
+            velocity: Velocity::new(Vector::ZERO),
+            collider: Collider::new(SaucerSize::Large.hit_radius(), &object_vertices),
+            renderable: Renderable::new(object_vertices, Color::from_rgba(174, 214, 241, 1.0)),
+            // Saucers leave once they've crossed the screen, but wrap vertically
+            wrap: Wrap::Vertical,
+            bullets: Pool::fixed(vec![Bullet::new(); NUM_BULLETS]),
+            fire_cooldown: 0,
+            course_cooldown: 0,
+        }
+    }
+
+    fn generate_vertices(size: SaucerSize) -> Vec<Vector> {
+        // Classic saucer outline: a dome sitting on a flattened hull
+        [
+            v!(-1.0, 0.0), v!(-0.4, -0.35), v!(-0.25, -0.7), v!(0.25, -0.7),
+            v!(0.4, -0.35), v!(1.0, 0.0), v!(0.4, 0.35), v!(-0.4, 0.35),
+        ].iter()
+         .map(|x| x.multiply(size.scale()))
+         .collect()
+    }
+
+    /// Bring the saucer in from the left or right edge of the screen.
+    pub fn spawn(&mut self, size: SaucerSize, rng: &mut GameRng) {
+        let from_left = rand!(rng, 2) == 0;
+        let direction = if from_left { 1.0 } else { -1.0 };
+
+        let half = self.transform.translation;
+
+        self.alive = true;
+        self.size = size;
+        self.renderable.object_vertices = Saucer::generate_vertices(size);
+        self.collider = Collider::new(size.hit_radius(), &self.renderable.object_vertices);
+        self.transform.location = v!(-direction * half.x, randf!(rng, -half.y, half.y));
+        self.velocity.linear = v!(direction * size.speed(), 0.0);
+        self.fire_cooldown = size.fire_rate();
+        self.course_cooldown = rand!(rng, 30, 90);
+
+        systems::place(&mut self.renderable, &self.transform);
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    /// Steer and shoot.  The small saucer's aim tightens as the player's
+    /// score goes up, and with no `target` it shoots wild like the large one.
+    pub fn think(&mut self, target: Option<Vector>, score: i64, rng: &mut GameRng) {
+        if !self.alive {
+            return;
+        }
+
+        self.course_cooldown -= 1;
+        if self.course_cooldown <= 0 {
+            let speed = self.size.speed();
+            self.velocity.linear.y = match rand!(rng, 3) {
+                0 => -speed / 2.0,
+                1 => speed / 2.0,
+                _ => 0.0,
+            };
+            self.course_cooldown = rand!(rng, 30, 90);
+        }
+
+        self.fire_cooldown -= 1;
+        if self.fire_cooldown <= 0 {
+            let direction = match (self.size, target) {
+                (SaucerSize::Large, _) | (SaucerSize::Small, None) => v!(1.0, 0.0).rotate(randf!(rng, 0, 360)),
+                (SaucerSize::Small, Some(target)) => {
+                    let spread = (40.0 - score as f32 / 1000.0).max(4.0);
+                    let delta = math::wrapped_delta(target, self.transform.location, self.transform.playfield());
+
+                    // Right on top of the target there's nothing to aim at
+                    let aim = if delta == Vector::ZERO { v!(1.0, 0.0).rotate(randf!(rng, 0, 360)) } else { delta.normalize() };
+                    aim.rotate(randf!(rng, -spread, spread))
+                },
+            };
+
+            self.shoot_bullet(direction);
+            self.fire_cooldown = self.size.fire_rate();
+        }
+    }
+
+    fn shoot_bullet(&mut self, direction: Vector) {
+        let location = self.transform.screen();
+
+        if let Some((_, bullet)) = self.bullets.acquire() {
+            bullet.fire(location, direction.multiply(BULLET_SPEED), BULLET_LIFETIME);
+        }
+    }
+
+    pub fn check_bounds(&mut self) {
+        let size = self.transform.playfield();
+
+        match systems::wrap(&mut self.transform, self.wrap, size) {
+            Some(shift) => self.renderable.shift(shift),
+            None => self.alive = false,
+        }
+
+        // bullets are in world space
+        self.bullets.iter_mut().filter(|x| x.alive).for_each(|x| x.wrap(size));
+    }
+
+    pub fn check_collision(&self, vec: Vector, radius: f32) -> bool {
+        if !self.alive { return false }
+
+        math::wrapped_distance(self.transform.location, vec, self.transform.playfield()) < self.collider.hit_radius + radius
+    }
+
+    pub fn handle_collision(&mut self, rng: &mut GameRng, particles: &mut Particles) {
+        self.alive = false;
+
+        let count = rand!(rng, 6, 12);
+        Emitter::sparks(self.size.scale() / 2.0, 13).burst(particles, self.transform.location, count, rng);
+        Emitter::debris(self.renderable.color, 30).shatter(particles, self.transform.location, &self.renderable.object_vertices);
+    }
+
+    pub fn update(&mut self) {
+        if self.alive {
+            systems::integrate(&mut self.transform, &mut self.velocity);
+        }
+
+        systems::place(&mut self.renderable, &self.transform);
+
+        self.bullets.iter_mut().for_each(|x| x.update());
+    }
+}
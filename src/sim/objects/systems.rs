@@ -0,0 +1,89 @@
+use quicksilver::geom::Vector;
+
+use super::util::math::VectorMath;
+use super::components::{Lifetime, Renderable, Transform, Velocity, Wrap};
+
+// The behaviour shared between game objects, each working on just the
+// components it needs.  Objects decide which of these to run and when.
+
+/// Move by the velocity, first slowing to the speed limit if there is one.
+pub fn integrate(transform: &mut Transform, velocity: &mut Velocity) {
+    if let Some(max_speed) = velocity.max_speed {
+        velocity.linear = velocity.linear.limit(max_speed);
+    }
+
+    transform.location += velocity.linear;
+}
+
+/// Put the outline where its object is on screen.
+pub fn place(renderable: &mut Renderable, transform: &Transform) {
+    renderable.world_vertices = renderable.object_vertices.iter()
+                                                          .map(|x| *x + transform.location + transform.translation)
+                                                          .collect();
+}
+
+/// Turn the outline by the velocity's spin, ready for the next placing.
+pub fn spin(renderable: &mut Renderable, velocity: &Velocity) {
+    renderable.object_vertices = renderable.object_vertices.iter()
+                                                           .map(|x| x.rotate(velocity.spin))
+                                                           .collect();
+}
+
+/// Keep something on a playfield of the given size.  Returns how far it
+/// jumped, or `None` if it left for good.
+pub fn wrap(transform: &mut Transform, wrap: Wrap, playfield: Vector) -> Option<Vector> {
+    let before = transform.location;
+    let half = playfield.divide(2.0);
+    let location = &mut transform.location;
+
+    match wrap {
+        Wrap::Around => {
+            location.x = jump(location.x, half.x);
+            location.y = jump(location.y, half.y);
+        },
+        Wrap::Vertical => {
+            if location.x < -half.x || location.x > half.x {
+                return None;
+            }
+
+            location.y = jump(location.y, half.y);
+        },
+        Wrap::Screen => {
+            location.x = carry(location.x, playfield.x);
+            location.y = carry(location.y, playfield.y);
+        },
+    }
+
+    Some(transform.location - before)
+}
+
+// Past one edge of a span from -half to half, straight to the other
+fn jump(position: f32, half: f32) -> f32 {
+    if position < -half {
+        half
+    } else if position > half {
+        -half
+    } else {
+        position
+    }
+}
+
+// Past one edge of a span from zero to size, the same distance in from the other
+fn carry(mut position: f32, size: f32) -> f32 {
+    if position < 0.0 {
+        position += size;
+    }
+
+    // Not an else: a tiny overshoot can round up to exactly `size`
+    if position >= size {
+        position -= size;
+    }
+
+    position
+}
+
+/// Count down a tick, returning false once time is up.
+pub fn age(lifetime: &mut Lifetime) -> bool {
+    lifetime.ticks_left -= 1;
+    lifetime.ticks_left > 0
+}
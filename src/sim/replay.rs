@@ -0,0 +1,311 @@
+use std::io::{Error, ErrorKind, Result};
+
+use super::inputs::Inputs;
+use crate::config::game_config::GameConfig;
+
+const MAGIC: &[u8; 4] = b"AREP";
+
+// Bump whenever the input encoding or the simulation rules change, since an
+// older recording would load fine but play out differently.
+//   1: original format
+//   2: hyperspace and held fire inputs
+//   3: seeds drive ChaCha8 instead of StdRng
+//   4: the game config is recorded
+//   5: small saucers aim across the playfield's edges
+//   6: new asteroids can be hit on the tick they spawn
+//   7: auto-fire ignores presses and releases
+//   8: small saucers shoot wild while the ship is gone
+//   9: waves bigger than the asteroid pool spawn in full
+const VERSION: u8 = 9;
+const HEADER_LEN: usize = 4 + 1 + 8 + 4;
+const RUN_LEN: usize = 3;
+
+/// A recorded game: the RNG seed and config it was played with, plus every
+/// tick's inputs.
+///
+/// Inputs are stored run-length encoded since players tend to hold the same
+/// controls for many ticks in a row.  On disk a replay is laid out as:
+///
+/// | bytes | contents                         |
+/// |-------|----------------------------------|
+/// | 4     | magic `AREP`                     |
+/// | 1     | format version                   |
+/// | 8     | seed (little endian)             |
+/// | 4     | config length (little endian)    |
+/// | m     | config as TOML                   |
+/// | 4     | number of runs (little endian)   |
+/// | 3 * n | runs: tick count (u16) + inputs  |
+///
+/// Where only text can be stored, such as the browser's `localStorage`, the
+/// same bytes are written out as hex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    seed: u64,
+    config: GameConfig,
+    runs: Vec<(u16, u8)>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: &GameConfig) -> Self {
+        Self {
+            seed,
+            config: *config,
+            runs: vec![],
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The config the game was played with, which playback has to match.
+    pub fn config(&self) -> &GameConfig {
+        &self.config
+    }
+
+    /// Total number of ticks recorded.
+    pub fn len(&self) -> usize {
+        self.runs.iter().map(|(count, _)| *count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Append the inputs used for the next tick.
+    pub fn record(&mut self, inputs: &Inputs) {
+        let bits = inputs.to_bits();
+
+        match self.runs.last_mut() {
+            Some((count, last)) if *last == bits && *count < u16::MAX => *count += 1,
+            _ => self.runs.push((1, bits)),
+        }
+    }
+
+    pub fn playback(&self) -> Playback {
+        Playback {
+            replay: self.clone(),
+            run: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let config = self.config.serialize();
+        let mut bytes = Vec::with_capacity(HEADER_LEN + 4 + config.len() + self.runs.len() * RUN_LEN);
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        bytes.extend_from_slice(&(config.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(config.as_bytes());
+        bytes.extend_from_slice(&(self.runs.len() as u32).to_le_bytes());
+
+        for (count, bits) in self.runs.iter() {
+            bytes.extend_from_slice(&count.to_le_bytes());
+            bytes.push(*bits);
+        }
+
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN || &bytes[0..4] != MAGIC {
+            return Err(invalid_data("not a replay file"));
+        }
+
+        if bytes[4] != VERSION {
+            return Err(invalid_data(&format!("unsupported replay version {}", bytes[4])));
+        }
+
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&bytes[5..13]);
+
+        let (config, rest) = split_prefixed(&bytes[13..], 1)?;
+        let config = std::str::from_utf8(config).ok()
+                                                .and_then(|text| GameConfig::parse(text).ok())
+                                                .ok_or_else(|| invalid_data("replay config is unreadable"))?;
+
+        let (body, rest) = split_prefixed(rest, RUN_LEN)?;
+        if !rest.is_empty() {
+            return Err(invalid_data("replay file has trailing data"));
+        }
+
+        let runs: Vec<(u16, u8)> = body.chunks(RUN_LEN)
+                                       .map(|run| (u16::from_le_bytes([run[0], run[1]]), run[2]))
+                                       .collect();
+
+        // Recording never writes an empty run, and playing one would still use up a tick
+        if runs.iter().any(|(count, _)| *count == 0) {
+            return Err(invalid_data("replay file has an empty run"));
+        }
+
+        Ok(Self {
+            seed: u64::from_le_bytes(seed),
+            config,
+            runs,
+        })
+    }
+
+    /// The encoded replay as hex, for storing as text.
+    pub fn to_text(&self) -> String {
+        self.encode().iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    pub fn from_text(text: &str) -> Result<Self> {
+        let text = text.trim();
+
+        if !text.len().is_multiple_of(2) || !text.is_ascii() {
+            return Err(invalid_data("not a replay file"));
+        }
+
+        let bytes = (0..text.len()).step_by(2)
+                                   .map(|i| u8::from_str_radix(&text[i..i + 2], 16))
+                                   .collect::<std::result::Result<Vec<u8>, _>>()
+                                   .map_err(|_| invalid_data("not a replay file"))?;
+
+        Replay::decode(&bytes)
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        std::fs::write(path, self.encode())
+    }
+
+    /// Load a replay saved by `save`, or the text form copied out of the
+    /// browser's storage into a file.
+    pub fn load(path: &str) -> Result<Self> {
+        let bytes = std::fs::read(path)?;
+
+        if bytes.starts_with(MAGIC) {
+            Replay::decode(&bytes)
+        } else {
+            Replay::from_text(&String::from_utf8_lossy(&bytes))
+        }
+    }
+}
+
+// Split off a section that starts with its number of items, each `item_len`
+// bytes long, returning it and whatever follows
+fn split_prefixed(bytes: &[u8], item_len: usize) -> Result<(&[u8], &[u8])> {
+    let truncated = || invalid_data("replay file is truncated");
+
+    if bytes.len() < 4 {
+        return Err(truncated());
+    }
+
+    let (count, rest) = bytes.split_at(4);
+    let len = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize * item_len;
+
+    if rest.len() < len {
+        return Err(truncated());
+    }
+
+    Ok(rest.split_at(len))
+}
+
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+/// Feeds a recorded replay back one tick at a time.
+pub struct Playback {
+    replay: Replay,
+    run: usize,
+    offset: u16,
+}
+
+impl Playback {
+    pub fn seed(&self) -> u64 {
+        self.replay.seed
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.run >= self.replay.runs.len()
+    }
+}
+
+impl Iterator for Playback {
+    type Item = Inputs;
+
+    fn next(&mut self) -> Option<Inputs> {
+        let (count, bits) = *self.replay.runs.get(self.run)?;
+
+        self.offset += 1;
+        if self.offset >= count {
+            self.run += 1;
+            self.offset = 0;
+        }
+
+        Some(Inputs::from_bits(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_bytes() {
+        let mut replay = Replay::new(1234, &GameConfig::default());
+        let fire = Inputs { fire: true, ..Inputs::new() };
+
+        replay.record(&Inputs::new());
+        replay.record(&Inputs::new());
+        replay.record(&fire);
+
+        let decoded = Replay::decode(&replay.encode()).unwrap();
+
+        assert_eq!(decoded, replay);
+        assert_eq!(decoded.playback().collect::<Vec<_>>(), vec![Inputs::new(), Inputs::new(), fire]);
+    }
+
+    #[test]
+    fn round_trips_through_text() {
+        let mut replay = Replay::new(u64::MAX, &GameConfig::default());
+        replay.record(&Inputs { thrust: true, ..Inputs::new() });
+
+        assert_eq!(Replay::from_text(&replay.to_text()).unwrap(), replay);
+        assert!(Replay::from_text("not hex").is_err());
+    }
+
+    #[test]
+    fn records_the_config() {
+        let config = GameConfig::parse_overrides("test", "[player]\nthrust = 0.8\nfire_mode = \"auto\"").unwrap();
+        let decoded = Replay::decode(&Replay::new(1, &config).encode()).unwrap();
+
+        assert_eq!(decoded.config(), &config);
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let mut replay = Replay::new(1, &GameConfig::default());
+        replay.record(&Inputs::new());
+        let bytes = replay.encode();
+
+        assert!(Replay::decode(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Replay::decode(&bytes[..HEADER_LEN + 10]).is_err());
+    }
+
+    #[test]
+    fn rejects_empty_runs() {
+        let mut replay = Replay::new(1, &GameConfig::default());
+        replay.record(&Inputs::new());
+        replay.record(&Inputs { fire: true, ..Inputs::new() });
+
+        let mut bytes = replay.encode();
+        let last_run = bytes.len() - RUN_LEN;
+        bytes[last_run] = 0;
+        bytes[last_run + 1] = 0;
+
+        assert!(Replay::decode(&bytes).is_err());
+        assert!(Replay::from_text(&bytes.iter().map(|byte| format!("{:02x}", byte)).collect::<String>()).is_err());
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let mut bytes = Replay::new(1, &GameConfig::default()).encode();
+        bytes[4] = VERSION - 1;
+
+        assert!(Replay::decode(&bytes).is_err());
+    }
+}
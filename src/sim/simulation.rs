@@ -0,0 +1,611 @@
+use quicksilver::geom::Vector;
+
+use super::objects::player::Player;
+use super::objects::asteroids::{Asteroid, Sizes};
+use super::objects::particles::Particles;
+use super::objects::saucer::{Saucer, SaucerSize};
+use crate::config::game_config::{FireMode, GameConfig};
+use crate::util::math::{self, VectorMath};
+use crate::util::pool::{Handle, Pool};
+use crate::util::rng::GameRng;
+use crate::util::spatial_hash::SpatialHash;
+use crate::{rand, randf};
+use rand::Rng;
+use super::inputs::Inputs;
+use super::waves::{self, Wave};
+
+// Ticks of calm between clearing a wave and the next one arriving (30 ticks per second)
+const WAVE_DELAY: i32 = 90;
+
+// Chance of the ship breaking up on leaving hyperspace, plus a little more
+// for every asteroid still out there
+const HYPERSPACE_RISK: f32 = 0.02;
+const HYPERSPACE_RISK_PER_ASTEROID: f32 = 0.02;
+const HYPERSPACE_RISK_MAX: f32 = 0.5;
+
+// Nothing may be this close to the centre when a new ship appears
+const SAFE_SPAWN_RADIUS: f32 = 120.0;
+
+// Roughly the size of a large asteroid, so most queries only touch a few cells
+const GRID_CELL_SIZE: f32 = 64.0;
+
+// Bullets are treated as this big when checked against the saucer
+const BULLET_RADIUS: f32 = 1.0;
+
+// How much of a glancing blow's sliding speed turns into spin, and the
+// fastest an asteroid may spin (degrees per tick)
+const SPIN_TRANSFER: f32 = 0.5;
+const MAX_SPIN: f32 = 4.0;
+
+// Particles alive at once across every explosion and exhaust trail
+const PARTICLE_BUDGET: usize = 1024;
+
+/// Anything asteroids and the saucer can run into.  The ordering is the order
+/// collisions are resolved in, which matters since later ones see the results
+/// of earlier ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Body {
+    Player,
+    PlayerBullet(Handle),
+    SaucerBullet(Handle),
+    Saucer,
+}
+
+/// Headless game state.  Owns everything that affects the outcome of a game
+/// and advances it one tick at a time, without any knowledge of windows,
+/// graphics or keyboards.
+pub struct Simulation {
+    pub window_size: Vector,
+    pub config: GameConfig,
+    pub player: Player,
+    pub asteroids: Pool<Asteroid>,
+    pub saucer: Saucer,
+    pub particles: Particles,
+    pub score: i64,
+    pub ticks: u64,
+    pub rng: GameRng,
+    wave: u32,
+    wave_countdown: i32,
+    saucer_cooldown: i32,
+    // Rebuilt every tick; kept around to reuse their memory
+    grid: SpatialHash<Body>,
+    asteroid_grid: SpatialHash<Handle>,
+}
+
+impl Simulation {
+    pub fn new(window_size: &Vector, config: &GameConfig, seed: u64) -> Self {
+        let mut rng = GameRng::new(seed);
+        let wave = waves::wave(1);
+
+        // The field starts empty and the first wave arrives after the usual delay
+        Simulation {
+            window_size: *window_size,
+            config: *config,
+            player: Player::new(window_size, &config.player),
+            asteroids: Simulation::initialize_asteroids(window_size, config, &wave, 0, &mut rng),
+            saucer: Saucer::new(window_size),
+            particles: Particles::new(window_size, PARTICLE_BUDGET),
+            score: 0,
+            ticks: 0,
+            wave: 1,
+            wave_countdown: WAVE_DELAY,
+            saucer_cooldown: rand!(rng, wave.saucer_interval.0, wave.saucer_interval.1),
+            rng,
+            grid: SpatialHash::new(*window_size, GRID_CELL_SIZE),
+            asteroid_grid: SpatialHash::new(*window_size, GRID_CELL_SIZE),
+        }
+    }
+
+    // The pool grows by a split's worth whenever it runs out, so no piece is
+    // ever lost, and starts big enough for the whole wave however large it is
+    fn initialize_asteroids(window_size: &Vector, config: &GameConfig, wave: &Wave, alive: usize, rng: &mut GameRng) -> Pool<Asteroid> {
+        let asteroids = (0..config.asteroids.pool_size.max(alive)).map(|i| Asteroid::new(window_size, &config.asteroids, i < alive, wave.speed, rng)).collect();
+        let (window_size, asteroid_config) = (*window_size, config.asteroids);
+
+        Pool::growable(asteroids, config.asteroids.split_count, move || Asteroid::empty(&window_size, &asteroid_config))
+    }
+
+    // Large asteroids at the start of a wave, swarm mode replacing the usual count
+    fn wave_asteroids(&self, wave: &Wave) -> usize {
+        match self.config.asteroids.swarm {
+            0 => wave.asteroids as usize,
+            swarm => swarm,
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.rng.seed()
+    }
+
+    pub fn lives(&self) -> i32 {
+        self.player.lives
+    }
+
+    /// The current 1-based wave number.
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    /// True during the pause before a wave arrives.
+    pub fn is_between_waves(&self) -> bool {
+        self.wave_countdown > 0
+    }
+
+    pub fn is_game_over(&self) -> bool {
+        !self.player.is_alive()
+    }
+
+    /// Advance the game by a single tick using the given inputs.
+    pub fn step(&mut self, inputs: &Inputs) {
+        self.apply_inputs(inputs);
+
+        let mut spawn_queue: Vec<(Sizes, Vector)> = vec![];
+
+        // Update Player
+        let spawn_clear = self.player.is_respawning() && self.is_spawn_clear();
+        self.player.update_respawn(spawn_clear);
+
+        if self.player.update_hyperspace() {
+            self.reenter_from_hyperspace();
+        }
+
+        self.player.update();
+        self.player.check_bounds();
+
+        // Start the next wave once the field has been cleared
+        self.update_wave();
+
+        // Update Saucer
+        self.update_saucer();
+
+        // Check for Collisions
+        let hull = self.player.hull();
+        let hull_radius = self.player.collider.bounding_radius;
+
+        self.build_grid(hull_radius);
+
+        for asteroid in self.asteroids.iter_mut() {
+            if asteroid.is_dead() {
+                continue;
+            }
+
+            for body in self.grid.query(asteroid.transform.location, asteroid.reach()) {
+                match body {
+                    // Handle Collision Between Player and Asteroid
+                    Body::Player => {
+                        if self.player.is_vulnerable() && asteroid.check_polygon_collision(self.player.transform.location, hull_radius, &hull) {
+                            self.player.handle_collsion(&mut self.rng, &mut self.particles);
+                        }
+                    },
+                    // Handle Collision Between Bullet and Asteroid
+                    Body::PlayerBullet(handle) => {
+                        let bullet = match self.player.bullets.get_mut(handle) {
+                            Some(bullet) => bullet,
+                            None => continue,
+                        };
+
+                        if bullet.is_alive() && asteroid.check_point_collision(bullet.transform.location - self.player.transform.translation) {
+                            bullet.handle_collision();
+
+                            self.score += match asteroid.size {
+                                Sizes::Large => self.config.scoring.large,
+                                Sizes::Medium => self.config.scoring.medium,
+                                Sizes::Small => self.config.scoring.small,
+                            };
+
+                            Simulation::destroy_asteroid(asteroid, &mut spawn_queue, &mut self.rng, &mut self.particles);
+                        }
+                    },
+                    // Handle Collision Between Saucer Bullet and Asteroid
+                    Body::SaucerBullet(handle) => {
+                        let bullet = match self.saucer.bullets.get_mut(handle) {
+                            Some(bullet) => bullet,
+                            None => continue,
+                        };
+
+                        if bullet.is_alive() && asteroid.check_point_collision(bullet.transform.location - self.saucer.transform.translation) {
+                            bullet.handle_collision();
+                            Simulation::destroy_asteroid(asteroid, &mut spawn_queue, &mut self.rng, &mut self.particles);
+                        }
+                    },
+                    // Handle Collision Between Saucer and Asteroid
+                    Body::Saucer => {
+                        if asteroid.is_alive() && self.saucer.check_collision(asteroid.transform.location, asteroid.collider.hit_radius) {
+                            self.saucer.handle_collision(&mut self.rng, &mut self.particles);
+                            Simulation::destroy_asteroid(asteroid, &mut spawn_queue, &mut self.rng, &mut self.particles);
+                        }
+                    },
+                }
+            }
+        }
+
+        // Handle Collision Between Player Bullet and Saucer
+        for body in self.grid.query(self.saucer.transform.location, self.saucer.collider.hit_radius) {
+            if let Body::PlayerBullet(handle) = body {
+                let bullet = match self.player.bullets.get_mut(handle) {
+                    Some(bullet) => bullet,
+                    None => continue,
+                };
+
+                if bullet.is_alive() && self.saucer.check_collision(bullet.transform.location - self.player.transform.translation, BULLET_RADIUS) {
+                    bullet.handle_collision();
+                    self.score += self.saucer.size.score();
+                    self.saucer.handle_collision(&mut self.rng, &mut self.particles);
+                }
+            }
+        }
+
+        // Handle Collision Between Saucer Bullet and Player
+        let playfield = self.window_size;
+
+        for body in self.grid.query(self.player.transform.location, self.player.collider.hit_radius) {
+            if let Body::SaucerBullet(handle) = body {
+                let bullet = match self.saucer.bullets.get_mut(handle) {
+                    Some(bullet) => bullet,
+                    None => continue,
+                };
+                let location = bullet.transform.location - self.saucer.transform.translation;
+
+                if !self.player.is_vulnerable() {
+                    break;
+                }
+
+                if bullet.is_alive() && math::wrapped_distance(location, self.player.transform.location, playfield) < self.player.collider.hit_radius {
+                    bullet.handle_collision();
+                    self.player.handle_collsion(&mut self.rng, &mut self.particles);
+                }
+            }
+        }
+
+        // Handle Collision Between Saucer and Player
+        let saucer_nearby = self.grid.query(self.player.transform.location, self.player.collider.hit_radius).contains(&Body::Saucer);
+
+        if saucer_nearby && self.player.is_vulnerable() && self.saucer.check_collision(self.player.transform.location, self.player.collider.hit_radius) {
+            self.saucer.handle_collision(&mut self.rng, &mut self.particles);
+            self.player.handle_collsion(&mut self.rng, &mut self.particles);
+        }
+
+        // Spawn Smaller Asteroids
+        while let Some((size, location)) = spawn_queue.pop() {
+            let speed = waves::wave(self.wave).speed;
+
+            // Reuse dead asteroids for spawning
+            for _ in 0..self.config.asteroids.split_count {
+                if let Some((_, asteroid)) = self.asteroids.acquire() {
+                    asteroid.spawn_asteroid(&location, &size, speed, &mut self.rng);
+                }
+            }
+        }
+
+        // Update Asteroids
+        for asteroid in self.asteroids.iter_mut() {
+            asteroid.update();
+            asteroid.check_bounds();
+        }
+
+        if self.config.asteroids.bounce {
+            self.bounce_asteroids();
+        }
+
+        self.particles.update();
+
+        self.ticks += 1;
+    }
+
+    // Sort everything asteroids can hit into the grid.  Bodies that can't
+    // collide with anything this tick are left out.
+    fn build_grid(&mut self, hull_radius: f32) {
+        self.grid.clear();
+
+        if self.player.is_vulnerable() {
+            self.grid.insert(Body::Player, self.player.transform.location, hull_radius);
+        }
+
+        for (handle, bullet) in self.player.bullets.alive() {
+            self.grid.insert(Body::PlayerBullet(handle), bullet.transform.location - self.player.transform.translation, BULLET_RADIUS);
+        }
+
+        for (handle, bullet) in self.saucer.bullets.alive() {
+            self.grid.insert(Body::SaucerBullet(handle), bullet.transform.location - self.saucer.transform.translation, BULLET_RADIUS);
+        }
+
+        if self.saucer.is_alive() {
+            self.grid.insert(Body::Saucer, self.saucer.transform.location, self.saucer.collider.hit_radius);
+        }
+    }
+
+    // Sort live asteroids into their own grid, by the circle they collide with
+    fn build_asteroid_grid(&mut self) {
+        self.asteroid_grid.clear();
+
+        for (handle, asteroid) in self.asteroids.alive() {
+            self.asteroid_grid.insert(handle, asteroid.transform.location, asteroid.collider.hit_radius);
+        }
+    }
+
+    // Elastic collisions between touching asteroids, treating them as
+    // circles.  Pairs are resolved in slot order to keep replays exact.
+    fn bounce_asteroids(&mut self) {
+        self.build_asteroid_grid();
+
+        let handles: Vec<Handle> = self.asteroids.alive().map(|(handle, _)| handle).collect();
+
+        for a in handles {
+            let nearby = match self.asteroids.get(a) {
+                Some(asteroid) => self.asteroid_grid.query(asteroid.transform.location, asteroid.collider.hit_radius),
+                None => continue,
+            };
+
+            for b in nearby.into_iter().filter(|b| *b > a) {
+                if let Some((first, second)) = self.asteroids.pair_mut(a, b) {
+                    Simulation::bounce(first, second, self.window_size);
+                }
+            }
+        }
+    }
+
+    fn bounce(a: &mut Asteroid, b: &mut Asteroid, playfield: Vector) {
+        // From a to b, the short way round if they're touching across an edge
+        let delta = math::wrapped_delta(b.transform.location, a.transform.location, playfield);
+        let distance = delta.magnitude();
+        let overlap = a.collider.hit_radius + b.collider.hit_radius - distance;
+
+        // Pieces of the same asteroid start out in the same spot, with no
+        // way to tell which way to push them
+        if overlap <= 0.0 || distance == 0.0 {
+            return;
+        }
+
+        let normal = delta.divide(distance);
+        let (mass_a, mass_b) = (a.size.mass(), b.size.mass());
+        let total = mass_a + mass_b;
+
+        // Separate them, the lighter one moving further
+        a.nudge(normal.multiply(-overlap * mass_b / total));
+        b.nudge(normal.multiply(overlap * mass_a / total));
+
+        let relative = a.velocity.linear - b.velocity.linear;
+        let closing = relative.dot(normal);
+
+        // Already moving apart
+        if closing <= 0.0 {
+            return;
+        }
+
+        a.velocity.linear -= normal.multiply(2.0 * mass_b / total * closing);
+        b.velocity.linear += normal.multiply(2.0 * mass_a / total * closing);
+
+        // Scraping past each other sets them spinning in opposite directions
+        let tangent = Vector::new(-normal.y, normal.x);
+        let slide = relative.dot(tangent) * SPIN_TRANSFER;
+
+        a.velocity.spin = (a.velocity.spin + slide * mass_b / total).clamp(-MAX_SPIN, MAX_SPIN);
+        b.velocity.spin = (b.velocity.spin - slide * mass_a / total).clamp(-MAX_SPIN, MAX_SPIN);
+    }
+
+    fn update_wave(&mut self) {
+        if self.wave_countdown > 0 {
+            self.wave_countdown -= 1;
+
+            if self.wave_countdown == 0 {
+                let wave = waves::wave(self.wave);
+                let alive = self.wave_asteroids(&wave);
+                self.asteroids = Simulation::initialize_asteroids(&self.window_size, &self.config, &wave, alive, &mut self.rng);
+            }
+        } else if self.asteroids.alive_count() == 0 {
+            self.wave += 1;
+            self.wave_countdown = WAVE_DELAY;
+        }
+    }
+
+    fn update_saucer(&mut self) {
+        if !self.saucer.is_alive() {
+            self.saucer_cooldown -= 1;
+
+            if self.saucer_cooldown <= 0 {
+                // The small, accurate saucer shows up more often as the score climbs
+                let small_chance = (self.score as f32 / 20000.0).min(0.8);
+                let size = if self.rng.gen::<f32>() < small_chance { SaucerSize::Small } else { SaucerSize::Large };
+
+                let (min, max) = waves::wave(self.wave).saucer_interval;
+
+                self.saucer.spawn(size, &mut self.rng);
+                self.saucer_cooldown = rand!(self.rng, min, max);
+            }
+        }
+
+        // There's nothing to aim at while the ship is gone or between places
+        let visible = self.player.is_alive() && !self.player.is_respawning() && !self.player.is_in_hyperspace();
+        let target = if visible { Some(self.player.transform.location) } else { None };
+
+        self.saucer.think(target, self.score, &mut self.rng);
+        self.saucer.update();
+        self.saucer.check_bounds();
+    }
+
+    fn is_spawn_clear(&mut self) -> bool {
+        let spawn = Vector::ZERO;
+
+        self.build_asteroid_grid();
+
+        let asteroids = &self.asteroids;
+        let asteroids_clear = self.asteroid_grid.query(spawn, SAFE_SPAWN_RADIUS)
+                                                .into_iter()
+                                                .filter_map(|handle| asteroids.get(handle))
+                                                .all(|a| !a.check_collision(spawn, SAFE_SPAWN_RADIUS));
+
+        // There's only ever one saucer, so a grid has nothing to save here
+        asteroids_clear && !self.saucer.check_collision(spawn, SAFE_SPAWN_RADIUS)
+    }
+
+    fn hyperspace(&mut self) {
+        if !self.player.can_hyperspace() {
+            return;
+        }
+
+        let bounds = self.player.transform.translation;
+        let destination = Vector::new(randf!(self.rng, -bounds.x, bounds.x), randf!(self.rng, -bounds.y, bounds.y));
+
+        self.player.enter_hyperspace(destination, &mut self.particles);
+    }
+
+    fn reenter_from_hyperspace(&mut self) {
+        let alive = self.asteroids.alive_count();
+        let risk = (HYPERSPACE_RISK + alive as f32 * HYPERSPACE_RISK_PER_ASTEROID).min(HYPERSPACE_RISK_MAX);
+
+        if self.rng.gen::<f32>() < risk {
+            self.player.handle_collsion(&mut self.rng, &mut self.particles);
+        }
+    }
+
+    fn destroy_asteroid(asteroid: &mut Asteroid, spawn_queue: &mut Vec<(Sizes, Vector)>, rng: &mut GameRng, particles: &mut Particles) {
+        asteroid.handle_collision(rng, particles);
+
+        // If an asteroid is destroyed, queue a smaller version to be spawned
+        if asteroid.size != Sizes::Small {
+            spawn_queue.push((asteroid.size, asteroid.transform.location));
+        }
+    }
+
+    fn apply_inputs(&mut self, inputs: &Inputs) {
+        if self.player.is_respawning() {
+            return;
+        }
+
+        if inputs.rotate_left {
+            self.player.rotate(-self.config.player.rotate_speed);
+        }
+
+        if inputs.rotate_right {
+            self.player.rotate(self.config.player.rotate_speed);
+        }
+
+        if inputs.hyperspace {
+            self.hyperspace();
+        }
+
+        // Nothing but turning while the ship is between places
+        if self.player.is_in_hyperspace() {
+            return;
+        }
+
+        if inputs.thrust {
+            self.player.apply_thrust(&mut self.particles);
+        }
+
+        // Auto-fire follows the button being held alone, so neither the press
+        // nor the release can slip in a shot between the cadence's own
+        let fire = match self.config.player.fire_mode {
+            FireMode::Tap => inputs.fire,
+            FireMode::Auto => inputs.fire_held,
+        };
+
+        if fire {
+            self.player.shoot_bullet();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEEDS: [u64; 5] = [1, 2, 3, 42, 0xDEAD_BEEF];
+    const TICKS: u64 = 3000;
+
+    // Turn, thrust, fire and jump in a fixed pattern that shifts over time
+    fn scripted_inputs(tick: u64) -> Inputs {
+        Inputs {
+            rotate_left: tick % 90 < 20,
+            rotate_right: tick % 150 > 130,
+            thrust: tick % 60 < 10,
+            fire: tick.is_multiple_of(7),
+            hyperspace: tick % 500 == 250,
+            fire_held: false,
+        }
+    }
+
+    fn play(seed: u64) -> Simulation {
+        let mut sim = Simulation::new(&Vector::new(1024.0, 768.0), &GameConfig::default(), seed);
+
+        for tick in 0..TICKS {
+            if sim.is_game_over() {
+                break;
+            }
+
+            sim.step(&scripted_inputs(tick));
+        }
+
+        sim
+    }
+
+    fn asteroid_positions(sim: &Simulation) -> Vec<(u32, u32, bool)> {
+        sim.asteroids.iter()
+                     .map(|a| (a.transform.location.x.to_bits(), a.transform.location.y.to_bits(), a.is_alive()))
+                     .collect()
+    }
+
+    // Shots the player fires while holding fire for `ticks`, then letting go
+    fn shots_while_held(ticks: u32) -> usize {
+        let config = GameConfig::parse_overrides("test", "[player]\nfire_mode = \"auto\"\nmax_bullets = 20").unwrap();
+        let mut sim = Simulation::new(&Vector::new(1024.0, 768.0), &config, 1);
+
+        while sim.player.is_respawning() {
+            sim.step(&Inputs::new());
+        }
+
+        let held = Inputs { fire_held: true, ..Inputs::new() };
+        let released = Inputs { fire: true, ..Inputs::new() };
+        let mut shots = std::collections::HashSet::new();
+
+        for inputs in (0..ticks).map(|_| &held).chain(std::iter::once(&released)) {
+            sim.step(inputs);
+            shots.extend(sim.player.bullets.alive().map(|(handle, _)| handle));
+        }
+
+        shots.len()
+    }
+
+    #[test]
+    fn auto_fire_keeps_to_the_fire_rate() {
+        let rate = GameConfig::default().player.fire_rate as u32;
+
+        for ticks in [1, rate - 1, rate, rate + 1, 7 * rate + 2].iter() {
+            let expected = ticks.div_ceil(rate) as usize;
+            assert_eq!(shots_while_held(*ticks), expected, "holding fire for {} ticks", ticks);
+        }
+    }
+
+    #[test]
+    fn waves_outgrow_a_small_pool() {
+        let config = GameConfig::parse_overrides("test", "[asteroids]\npool_size = 1").unwrap();
+        let mut sim = Simulation::new(&Vector::new(1024.0, 768.0), &config, 1);
+
+        while sim.asteroids.alive_count() == 0 {
+            sim.step(&Inputs::new());
+        }
+
+        assert_eq!(sim.asteroids.alive_count(), waves::wave(1).asteroids as usize);
+    }
+
+    #[test]
+    fn same_seed_plays_out_the_same() {
+        for seed in SEEDS.iter() {
+            let first = play(*seed);
+            let second = play(*seed);
+
+            assert_eq!(first.score, second.score, "score differs for seed {}", seed);
+            assert_eq!(first.ticks, second.ticks, "tick count differs for seed {}", seed);
+            assert_eq!(first.lives(), second.lives(), "lives differ for seed {}", seed);
+            assert_eq!(asteroid_positions(&first), asteroid_positions(&second), "asteroids differ for seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn scripted_games_get_somewhere() {
+        // Guards against the test above passing because nothing happens
+        let scores: Vec<i64> = SEEDS.iter().map(|seed| play(*seed).score).collect();
+
+        assert!(scores.iter().any(|score| *score > 0), "no game scored: {:?}", scores);
+    }
+}
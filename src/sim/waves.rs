@@ -0,0 +1,26 @@
+/// Difficulty settings for a single wave.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wave {
+    /// Large asteroids at the start of the wave
+    pub asteroids: u8,
+    /// Range of each component of an asteroid's velocity, in pixels per tick
+    pub speed: (f32, f32),
+    /// Range of ticks between saucer appearances
+    pub saucer_interval: (i32, i32),
+}
+
+// Waves past the end of the table repeat the last entry
+const WAVES: &[Wave] = &[
+    Wave { asteroids: 3, speed: (0.0, 0.5), saucer_interval: (450, 900) },
+    Wave { asteroids: 4, speed: (0.1, 0.7), saucer_interval: (420, 840) },
+    Wave { asteroids: 5, speed: (0.2, 0.9), saucer_interval: (390, 780) },
+    Wave { asteroids: 6, speed: (0.3, 1.1), saucer_interval: (360, 720) },
+    Wave { asteroids: 7, speed: (0.4, 1.3), saucer_interval: (330, 660) },
+    Wave { asteroids: 8, speed: (0.5, 1.5), saucer_interval: (300, 600) },
+];
+
+/// Settings for the given 1-based wave number.
+pub fn wave(number: u32) -> Wave {
+    let index = (number.max(1) as usize - 1).min(WAVES.len() - 1);
+    WAVES[index]
+}
@@ -0,0 +1,110 @@
+use quicksilver::{
+    geom::{Rectangle, Transform, Vector},
+    graphics::{Color, Graphics, ResizeHandler},
+};
+
+use crate::v;
+
+/// Size of the logical playfield.  Everything in the game is laid out in
+/// these units, whatever the size of the window or canvas.
+pub const PLAYFIELD_WIDTH: f32 = 1024.0;
+pub const PLAYFIELD_HEIGHT: f32 = 768.0;
+
+/// The size of the window given the one quicksilver `reported`.
+///
+/// HACK: Quicksilver reports the wrong window size when deployed to WASM, so
+/// there the canvas is asked instead, falling back to the playfield's own
+/// size as the game always used to assume.
+#[cfg(target_arch = "wasm32")]
+pub fn actual_window_size(_reported: Vector) -> Vector {
+    use stdweb::traits::*;
+    use stdweb::unstable::TryInto;
+    use stdweb::web::document;
+    use stdweb::web::html_element::CanvasElement;
+
+    let canvas: Option<CanvasElement> = document().query_selector("canvas")
+                                                  .ok()
+                                                  .flatten()
+                                                  .and_then(|canvas| canvas.try_into().ok());
+
+    match canvas {
+        Some(canvas) if canvas.width() > 0 && canvas.height() > 0 => v!(canvas.width() as f32, canvas.height() as f32),
+        _ => v!(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn actual_window_size(reported: Vector) -> Vector {
+    reported
+}
+
+/// Maps the logical playfield onto the window, scaling it to fit and
+/// letterboxing whatever is left over.
+pub struct Camera {
+    playfield: Vector,
+    window_size: Vector,
+}
+
+impl Camera {
+    pub fn new() -> Self {
+        let playfield = v!(PLAYFIELD_WIDTH, PLAYFIELD_HEIGHT);
+
+        Self {
+            playfield,
+            window_size: playfield,
+        }
+    }
+
+    pub fn playfield(&self) -> Vector {
+        self.playfield
+    }
+
+    fn resize_handler(&self) -> ResizeHandler {
+        ResizeHandler::Fit {
+            aspect_width: self.playfield.x,
+            aspect_height: self.playfield.y,
+        }
+    }
+
+    /// Update the projection for a new window size.
+    pub fn resize(&mut self, gfx: &mut Graphics, window_size: Vector) {
+        self.window_size = window_size;
+
+        let projection = Transform::orthographic(Rectangle::new_sized(self.playfield));
+        gfx.set_projection(self.resize_handler().projection(window_size) * projection);
+    }
+
+    /// Convert a point in window coordinates, such as a pointer location,
+    /// to playfield coordinates.
+    pub fn to_playfield(&self, point: Vector) -> Vector {
+        let content = self.resize_handler().content_size(self.window_size);
+        let offset = (self.window_size - content) * 0.5;
+
+        (point - offset) * (self.playfield.x / content.x)
+    }
+
+    /// Cover anything drawn outside the playfield, such as objects wrapping
+    /// around the edges, so the letterbox bars stay clean.
+    pub fn draw_letterbox(&self, gfx: &mut Graphics) {
+        let content = self.resize_handler().content_size(self.window_size);
+        if content.x <= 0.0 || content.y <= 0.0 {
+            return;
+        }
+
+        // How far the window extends past the playfield, in playfield units
+        let scale = self.playfield.x / content.x;
+        let margin = (self.window_size - content).times(v!(scale, scale)) * 0.5 + v!(1.0, 1.0);
+        let (w, h) = (self.playfield.x, self.playfield.y);
+
+        let bars = [
+            Rectangle::new(v!(-margin.x, -margin.y), v!(w + margin.x * 2.0, margin.y)),
+            Rectangle::new(v!(-margin.x, h), v!(w + margin.x * 2.0, margin.y)),
+            Rectangle::new(v!(-margin.x, 0.0), v!(margin.x, h)),
+            Rectangle::new(v!(w, 0.0), v!(margin.x, h)),
+        ];
+
+        for bar in bars.iter() {
+            gfx.fill_rect(bar, Color::BLACK);
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use quicksilver::geom::Vector;
+
+use super::math::VectorMath;
+
+// Polygons here are lists of vertices in order, either open or closed (with
+// the first vertex repeated at the end).  They don't need to be convex.
+
+/// Radius of the smallest circle around the origin containing every vertex.
+pub fn bounding_radius(vertices: &[Vector]) -> f32 {
+    vertices.iter().map(|v| v.magnitude()).fold(0.0, f32::max)
+}
+
+/// Whether a point lies inside a polygon, by counting how many edges a ray
+/// cast from the point to the right crosses.
+pub fn point_in_polygon(point: Vector, polygon: &[Vector]) -> bool {
+    let mut inside = false;
+
+    for (a, b) in edges(polygon) {
+        if (a.y > point.y) != (b.y > point.y) {
+            let crossing_x = a.x + (point.y - a.y) / (b.y - a.y) * (b.x - a.x);
+
+            if point.x < crossing_x {
+                inside = !inside;
+            }
+        }
+    }
+
+    inside
+}
+
+/// Whether segments `a1`-`a2` and `b1`-`b2` cross.  Touching at an end or
+/// overlapping along a line counts.
+pub fn segments_intersect(a1: Vector, a2: Vector, b1: Vector, b2: Vector) -> bool {
+    let d1 = orientation(b1, b2, a1);
+    let d2 = orientation(b1, b2, a2);
+    let d3 = orientation(a1, a2, b1);
+    let d4 = orientation(a1, a2, b2);
+
+    if ((d1 > 0.0 && d2 < 0.0) || (d1 < 0.0 && d2 > 0.0)) && ((d3 > 0.0 && d4 < 0.0) || (d3 < 0.0 && d4 > 0.0)) {
+        return true;
+    }
+
+    (d1 == 0.0 && on_segment(b1, b2, a1))
+        || (d2 == 0.0 && on_segment(b1, b2, a2))
+        || (d3 == 0.0 && on_segment(a1, a2, b1))
+        || (d4 == 0.0 && on_segment(a1, a2, b2))
+}
+
+/// Whether two polygons overlap: either their edges cross or one is
+/// entirely inside the other.
+pub fn polygons_intersect(a: &[Vector], b: &[Vector]) -> bool {
+    let (first_a, first_b) = match (a.first(), b.first()) {
+        (Some(first_a), Some(first_b)) => (*first_a, *first_b),
+        _ => return false,
+    };
+
+    for (a1, a2) in edges(a) {
+        for (b1, b2) in edges(b) {
+            if segments_intersect(a1, a2, b1, b2) {
+                return true;
+            }
+        }
+    }
+
+    point_in_polygon(first_a, b) || point_in_polygon(first_b, a)
+}
+
+// Every edge, including the one closing the polygon
+fn edges(polygon: &[Vector]) -> impl Iterator<Item = (Vector, Vector)> + '_ {
+    let next = polygon.iter().cycle().skip(1);
+
+    polygon.iter().copied().zip(next.copied())
+}
+
+// Positive if `c` is to the left of the line through `a` and `b`, negative
+// if it's to the right and zero if it's on it
+fn orientation(a: Vector, b: Vector, c: Vector) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+// Whether `c`, known to be on the line through `a` and `b`, is between them
+fn on_segment(a: Vector, b: Vector, c: Vector) -> bool {
+    c.x >= a.x.min(b.x) && c.x <= a.x.max(b.x) && c.y >= a.y.min(b.y) && c.y <= a.y.max(b.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(points: &[(f32, f32)]) -> Vec<Vector> {
+        points.iter().map(|(x, y)| Vector::new(*x, *y)).collect()
+    }
+
+    fn square(half: f32, centre: (f32, f32)) -> Vec<Vector> {
+        let (x, y) = centre;
+        polygon(&[(x - half, y - half), (x + half, y - half), (x + half, y + half), (x - half, y + half)])
+    }
+
+    // A square with a slot cut into its right side
+    fn notched() -> Vec<Vector> {
+        polygon(&[(-40.0, -40.0), (40.0, -40.0), (40.0, -10.0), (0.0, -10.0), (0.0, 10.0), (40.0, 10.0), (40.0, 40.0), (-40.0, 40.0)])
+    }
+
+    #[test]
+    fn points_in_a_concave_polygon() {
+        let notched = notched();
+
+        assert!(point_in_polygon(Vector::new(-20.0, 0.0), &notched));
+        assert!(point_in_polygon(Vector::new(20.0, 25.0), &notched));
+        assert!(!point_in_polygon(Vector::new(20.0, 0.0), &notched));
+        assert!(!point_in_polygon(Vector::new(60.0, 0.0), &notched));
+    }
+
+    #[test]
+    fn rays_through_a_vertex_count_once() {
+        let diamond = polygon(&[(0.0, -10.0), (10.0, 0.0), (0.0, 10.0), (-10.0, 0.0)]);
+
+        assert!(point_in_polygon(Vector::new(0.0, 0.0), &diamond));
+        assert!(!point_in_polygon(Vector::new(-20.0, 0.0), &diamond));
+        assert!(!point_in_polygon(Vector::new(-20.0, 10.0), &diamond));
+        assert!(!point_in_polygon(Vector::new(-20.0, -10.0), &diamond));
+    }
+
+    #[test]
+    fn segments_touching_at_an_end_intersect() {
+        let (a, b, c) = (Vector::new(0.0, 0.0), Vector::new(1.0, 1.0), Vector::new(2.0, 0.0));
+
+        assert!(segments_intersect(a, b, b, c));
+        assert!(segments_intersect(a, c, Vector::new(1.0, 0.0), b));
+        assert!(!segments_intersect(a, b, c, Vector::new(3.0, 0.0)));
+    }
+
+    #[test]
+    fn collinear_segments_intersect_only_if_they_overlap() {
+        let on_x = |x: f32| Vector::new(x, 0.0);
+
+        assert!(segments_intersect(on_x(0.0), on_x(2.0), on_x(1.0), on_x(3.0)));
+        assert!(segments_intersect(on_x(0.0), on_x(3.0), on_x(1.0), on_x(2.0)));
+        assert!(!segments_intersect(on_x(0.0), on_x(1.0), on_x(2.0), on_x(3.0)));
+    }
+
+    #[test]
+    fn polygon_inside_another_intersects() {
+        let outer = square(50.0, (0.0, 0.0));
+        let inner = square(5.0, (10.0, -10.0));
+
+        assert!(polygons_intersect(&inner, &outer));
+        assert!(polygons_intersect(&outer, &inner));
+    }
+
+    #[test]
+    fn disjoint_polygons_dont_intersect() {
+        let notched = notched();
+
+        assert!(!polygons_intersect(&square(5.0, (100.0, 0.0)), &notched));
+        assert!(!polygons_intersect(&square(5.0, (25.0, 0.0)), &notched));
+        assert!(!polygons_intersect(&[], &notched));
+    }
+}
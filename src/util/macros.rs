@@ -7,26 +7,26 @@ macro_rules! v {
 
 #[macro_export]
 macro_rules! rand {
-    () => {
-        rand::thread_rng().gen::<u16>() as i32
+    ($rng:expr) => {
+        $rng.gen::<u16>() as i32
     };
-    ($e:expr) => {
-        rand::thread_rng().gen_range(0, $e) as i32
+    ($rng:expr, $e:expr) => {
+        $rng.gen_range(0, $e) as i32
     };
-    ($e:expr, $f:expr) => {
-        rand::thread_rng().gen_range($e, $f) as i32
+    ($rng:expr, $e:expr, $f:expr) => {
+        $rng.gen_range($e, $f) as i32
     };
 }
 
 #[macro_export]
 macro_rules! randf {
-    () => {
-        rand::thread_rng().gen::<u16>() as f32
+    ($rng:expr) => {
+        $rng.gen::<u16>() as f32
     };
-    ($e:expr) => {
-        rand::thread_rng().gen_range(0, $e) as f32
+    ($rng:expr, $e:expr) => {
+        $rng.gen_range(0, $e) as f32
     };
-    ($e:expr, $f:expr) => {
-        rand::thread_rng().gen_range($e, $f) as f32
+    ($rng:expr, $e:expr, $f:expr) => {
+        $rng.gen_range($e, $f) as f32
     };
-}
\ No newline at end of file
+}
@@ -13,11 +13,11 @@ pub trait VectorMath {
 
 impl VectorMath for Vector {
     fn multiply(&self, rhs: f32) -> Vector {
-        v!(&self.x * rhs, &self.y * rhs)
+        v!(self.x * rhs, self.y * rhs)
     }
 
     fn divide(&self, rhs: f32) -> Vector {
-        v!(&self.x / rhs, &self.y / rhs)
+        v!(self.x / rhs, self.y / rhs)
     }
 
     fn rotate(&self, degrees: f32) -> Vector {
@@ -32,7 +32,7 @@ impl VectorMath for Vector {
     }
 
     fn magnitude(&self) -> f32 {
-        let c2 = &self.x.powf(2.0) + &self.y.powf(2.0);
+        let c2 = self.x.powf(2.0) + self.y.powf(2.0);
         c2.sqrt()
     }
 
@@ -51,7 +51,64 @@ impl VectorMath for Vector {
                 y: self.y * ratio,
             }
         } else {
-            self.clone()
+            *self
         }
     }
-}
\ No newline at end of file
+}
+
+/// Shortest vector from `b` to `a` on a playfield of `size` whose edges wrap
+/// around, so objects near opposite edges are close together.
+pub fn wrapped_delta(a: Vector, b: Vector, size: Vector) -> Vector {
+    let wrap = |d: f32, size: f32| {
+        if d > size / 2.0 {
+            d - size
+        } else if d < -size / 2.0 {
+            d + size
+        } else {
+            d
+        }
+    };
+
+    v!(wrap(a.x - b.x, size.x), wrap(a.y - b.y, size.y))
+}
+
+pub fn wrapped_distance(a: Vector, b: Vector, size: Vector) -> f32 {
+    wrapped_delta(a, b, size).magnitude()
+}
+
+/// Offsets at which to draw extra copies of an object reaching `radius` from
+/// `location` so the parts hanging over an edge show on the opposite side.
+/// `location` is relative to the centre of a playfield of `size`.
+pub fn ghost_offsets(location: Vector, radius: f32, size: Vector) -> Vec<Vector> {
+    let half = size.divide(2.0);
+
+    let axis = |position: f32, half: f32, size: f32| {
+        if position + radius > half {
+            Some(-size)
+        } else if position - radius < -half {
+            Some(size)
+        } else {
+            None
+        }
+    };
+
+    let x = axis(location.x, half.x, size.x);
+    let y = axis(location.y, half.y, size.y);
+
+    let mut offsets = vec![];
+
+    if let Some(x) = x {
+        offsets.push(v!(x, 0.0));
+    }
+
+    if let Some(y) = y {
+        offsets.push(v!(0.0, y));
+    }
+
+    // Straddling a corner shows up in the diagonally opposite one too
+    if let (Some(x), Some(y)) = (x, y) {
+        offsets.push(v!(x, y));
+    }
+
+    offsets
+}
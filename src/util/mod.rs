@@ -1,2 +1,7 @@
+pub mod camera;
+pub mod collision;
 pub mod math;
+pub mod pool;
+pub mod rng;
+pub mod spatial_hash;
 #[macro_use] pub mod macros;
@@ -0,0 +1,277 @@
+use std::fmt;
+use std::rc::Rc;
+
+/// Anything kept in a `Pool`.  Objects live and die by their own flag, e.g.
+/// a bullet dies when it hits something, and dead ones get reused.
+pub trait Poolable {
+    fn is_alive(&self) -> bool;
+}
+
+/// Refers to one object in a pool, until its slot is reused for another.
+/// Handles sort in slot order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u32,
+}
+
+// What to do when every slot is in use
+enum Growth<T> {
+    Fixed,
+    By(usize, Rc<dyn Fn() -> T>),
+}
+
+impl<T> Clone for Growth<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Growth::Fixed => Growth::Fixed,
+            Growth::By(step, make) => Growth::By(*step, Rc::clone(make)),
+        }
+    }
+}
+
+/// A set of reusable objects.
+///
+/// Dead objects are handed out again lowest slot first, so the order things
+/// are spawned and updated in, and with it the simulation, is repeatable.
+/// A fixed pool refuses to hand anything out when it's full; a growable one
+/// adds more slots.
+#[derive(Clone)]
+pub struct Pool<T> {
+    slots: Vec<T>,
+    generations: Vec<u32>,
+    growth: Growth<T>,
+}
+
+impl<T: fmt::Debug> fmt::Debug for Pool<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.slots.iter()).finish()
+    }
+}
+
+impl<T: Poolable> Pool<T> {
+    pub fn fixed(slots: Vec<T>) -> Self {
+        Self {
+            generations: vec![0; slots.len()],
+            slots,
+            growth: Growth::Fixed,
+        }
+    }
+
+    /// A pool that adds `step` slots made by `make` whenever it runs out.
+    pub fn growable(slots: Vec<T>, step: usize, make: impl Fn() -> T + 'static) -> Self {
+        Self {
+            generations: vec![0; slots.len()],
+            slots,
+            growth: Growth::By(step.max(1), Rc::new(make)),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn alive_count(&self) -> usize {
+        self.slots.iter().filter(|x| x.is_alive()).count()
+    }
+
+    /// Take the first dead slot, growing the pool if it's full and allowed
+    /// to.  The caller is expected to bring the object back to life, or the
+    /// same slot will be handed out again.
+    pub fn acquire(&mut self) -> Option<(Handle, &mut T)> {
+        let index = match self.slots.iter().position(|x| !x.is_alive()) {
+            Some(index) => index,
+            None => self.grow()?,
+        };
+
+        self.generations[index] = self.generations[index].wrapping_add(1);
+
+        let handle = Handle { index, generation: self.generations[index] };
+        Some((handle, &mut self.slots[index]))
+    }
+
+    /// Take over the slot of a live object, e.g. to make room when a fixed
+    /// pool is full.  Handles to the object it held stop working.
+    pub fn reclaim(&mut self, handle: Handle) -> Option<(Handle, &mut T)> {
+        if !self.is_current(handle) {
+            return None;
+        }
+
+        let index = handle.index;
+        self.generations[index] = self.generations[index].wrapping_add(1);
+
+        let handle = Handle { index, generation: self.generations[index] };
+        Some((handle, &mut self.slots[index]))
+    }
+
+    // Add slots, returning the first new one
+    fn grow(&mut self) -> Option<usize> {
+        match &self.growth {
+            Growth::Fixed => None,
+            Growth::By(step, make) => {
+                let first = self.slots.len();
+
+                for _ in 0..*step {
+                    self.slots.push(make());
+                    self.generations.push(0);
+                }
+
+                Some(first)
+            },
+        }
+    }
+
+    fn is_current(&self, handle: Handle) -> bool {
+        self.generations.get(handle.index) == Some(&handle.generation)
+    }
+
+    /// The object a handle refers to, if its slot hasn't been reused since.
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        if self.is_current(handle) { self.slots.get(handle.index) } else { None }
+    }
+
+    pub fn get_mut(&mut self, handle: Handle) -> Option<&mut T> {
+        if self.is_current(handle) { self.slots.get_mut(handle.index) } else { None }
+    }
+
+    /// Two different objects at once, e.g. to bounce them off each other.
+    pub fn pair_mut(&mut self, a: Handle, b: Handle) -> Option<(&mut T, &mut T)> {
+        if a.index == b.index || !self.is_current(a) || !self.is_current(b) {
+            return None;
+        }
+
+        if a.index < b.index {
+            let (head, tail) = self.slots.split_at_mut(b.index);
+            Some((&mut head[a.index], &mut tail[0]))
+        } else {
+            let (head, tail) = self.slots.split_at_mut(a.index);
+            Some((&mut tail[0], &mut head[b.index]))
+        }
+    }
+
+    /// Every object, dead or alive, e.g. to draw what's left of dead ones.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots.iter_mut()
+    }
+
+    /// Live objects and their handles, in slot order.
+    pub fn alive(&self) -> impl Iterator<Item = (Handle, &T)> {
+        let generations = &self.generations;
+
+        self.slots.iter()
+                  .enumerate()
+                  .filter(|(_, x)| x.is_alive())
+                  .map(move |(index, x)| (Handle { index, generation: generations[index] }, x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default, PartialEq)]
+    struct Thing {
+        alive: bool,
+        value: i32,
+    }
+
+    impl Poolable for Thing {
+        fn is_alive(&self) -> bool {
+            self.alive
+        }
+    }
+
+    // Acquire a slot and bring it to life with `value`
+    fn spawn(pool: &mut Pool<Thing>, value: i32) -> Option<Handle> {
+        let (handle, thing) = pool.acquire()?;
+        *thing = Thing { alive: true, value };
+
+        Some(handle)
+    }
+
+    #[test]
+    fn acquires_the_lowest_dead_slot() {
+        let mut pool = Pool::fixed(vec![Thing::default(); 4]);
+        let handles: Vec<Handle> = (0..3).map(|i| spawn(&mut pool, i).unwrap()).collect();
+
+        pool.get_mut(handles[0]).unwrap().alive = false;
+        pool.get_mut(handles[1]).unwrap().alive = false;
+
+        let (handle, _) = pool.acquire().unwrap();
+        assert_eq!(handle.index, 0);
+    }
+
+    #[test]
+    fn fixed_pools_refuse_when_full() {
+        let mut pool = Pool::fixed(vec![Thing::default(); 2]);
+
+        assert!(spawn(&mut pool, 1).is_some());
+        assert!(spawn(&mut pool, 2).is_some());
+        assert!(pool.acquire().is_none());
+        assert_eq!(pool.capacity(), 2);
+        assert_eq!(pool.alive_count(), 2);
+    }
+
+    #[test]
+    fn growable_pools_grow_by_step() {
+        let mut pool = Pool::growable(vec![Thing::default(); 2], 3, Thing::default);
+
+        spawn(&mut pool, 1);
+        spawn(&mut pool, 2);
+        let handle = spawn(&mut pool, 3).unwrap();
+
+        assert_eq!(handle.index, 2);
+        assert_eq!(pool.capacity(), 5);
+        assert_eq!(pool.get(handle).unwrap().value, 3);
+    }
+
+    #[test]
+    fn stale_handles_find_nothing() {
+        let mut pool = Pool::fixed(vec![Thing::default(); 1]);
+        let stale = spawn(&mut pool, 1).unwrap();
+
+        pool.get_mut(stale).unwrap().alive = false;
+        let current = spawn(&mut pool, 2).unwrap();
+
+        assert!(pool.get(stale).is_none());
+        assert!(pool.get_mut(stale).is_none());
+        assert_eq!(pool.get(current).unwrap().value, 2);
+    }
+
+    #[test]
+    fn reclaiming_invalidates_the_old_handle() {
+        let mut pool = Pool::fixed(vec![Thing::default(); 2]);
+        spawn(&mut pool, 1);
+        let old = spawn(&mut pool, 2).unwrap();
+
+        let (new, thing) = pool.reclaim(old).unwrap();
+        *thing = Thing { alive: true, value: 3 };
+
+        assert_eq!(new.index, old.index);
+        assert!(pool.get(old).is_none());
+        assert!(pool.reclaim(old).is_none());
+        assert_eq!(pool.get(new).unwrap().value, 3);
+    }
+
+    #[test]
+    fn pairs_must_be_two_current_handles() {
+        let mut pool = Pool::fixed(vec![Thing::default(); 3]);
+        let a = spawn(&mut pool, 1).unwrap();
+        let b = spawn(&mut pool, 2).unwrap();
+        let stale = spawn(&mut pool, 3).unwrap();
+
+        pool.get_mut(stale).unwrap().alive = false;
+        spawn(&mut pool, 4);
+
+        assert!(pool.pair_mut(a, a).is_none());
+        assert!(pool.pair_mut(a, stale).is_none());
+        assert!(pool.pair_mut(stale, b).is_none());
+
+        let (second, first) = pool.pair_mut(b, a).unwrap();
+        assert_eq!((second.value, first.value), (2, 1));
+    }
+}
@@ -0,0 +1,161 @@
+use quicksilver::geom::Vector;
+
+use super::math::VectorMath;
+
+/// A uniform grid over a playfield whose edges wrap, for quickly finding
+/// what might be near what.  Items are stored by the circle around them and
+/// a query returns everything whose circle could overlap the one asked
+/// about, so callers still need to do their own exact test.
+///
+/// Locations are relative to the centre of the playfield, like everything
+/// else in the simulation.
+pub struct SpatialHash<T> {
+    half: Vector,
+    cell_size: Vector,
+    columns: i32,
+    rows: i32,
+    cells: Vec<Vec<T>>,
+}
+
+impl<T: Copy + Ord> SpatialHash<T> {
+    /// Cells are roughly `cell_size` across, stretched slightly so a whole
+    /// number of them fits the playfield and the wrap lines up.
+    pub fn new(playfield: Vector, cell_size: f32) -> Self {
+        let columns = (playfield.x / cell_size).ceil().max(1.0) as i32;
+        let rows = (playfield.y / cell_size).ceil().max(1.0) as i32;
+
+        Self {
+            half: playfield.divide(2.0),
+            cell_size: Vector::new(playfield.x / columns as f32, playfield.y / rows as f32),
+            columns,
+            rows,
+            cells: vec![vec![]; (columns * rows) as usize],
+        }
+    }
+
+    /// Empty the grid, keeping its memory for the next round of inserts.
+    pub fn clear(&mut self) {
+        self.cells.iter_mut().for_each(|cell| cell.clear());
+    }
+
+    pub fn insert(&mut self, item: T, location: Vector, radius: f32) {
+        for index in self.cells_for(location, radius) {
+            self.cells[index].push(item);
+        }
+    }
+
+    /// Everything that might be within `radius` of `location`, sorted and
+    /// without repeats so results come out in a predictable order.
+    pub fn query(&self, location: Vector, radius: f32) -> Vec<T> {
+        let mut found: Vec<T> = self.cells_for(location, radius)
+                                    .flat_map(|index| self.cells[index].iter().copied())
+                                    .collect();
+
+        found.sort();
+        found.dedup();
+        found
+    }
+
+    // Every cell the circle's bounding box touches, wrapping at the edges
+    fn cells_for(&self, location: Vector, radius: f32) -> impl Iterator<Item = usize> {
+        let columns = span(location.x + self.half.x, radius, self.cell_size.x, self.columns);
+        let rows = span(location.y + self.half.y, radius, self.cell_size.y, self.rows);
+        let (column_count, row_count) = (self.columns, self.rows);
+
+        rows.flat_map(move |row| {
+            columns.clone().map(move |column| {
+                (row.rem_euclid(row_count) * column_count + column.rem_euclid(column_count)) as usize
+            })
+        })
+    }
+}
+
+// The range of cells covered from `position - radius` to `position + radius`
+// along one axis, never more than once around
+fn span(position: f32, radius: f32, cell_size: f32, count: i32) -> std::ops::Range<i32> {
+    let first = ((position - radius) / cell_size).floor() as i32;
+    let last = ((position + radius) / cell_size).floor() as i32;
+
+    if last - first + 1 >= count {
+        0..count
+    } else {
+        first..last + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use super::*;
+    use crate::util::math;
+    use crate::util::rng::GameRng;
+    use crate::randf;
+
+    const PLAYFIELD: Vector = Vector { x: 1000.0, y: 800.0 };
+
+    #[test]
+    fn finds_items_across_the_edges() {
+        let mut grid = SpatialHash::new(PLAYFIELD, 100.0);
+        grid.insert(1, Vector::new(495.0, 0.0), 10.0);
+        grid.insert(2, Vector::new(495.0, 395.0), 10.0);
+
+        assert_eq!(grid.query(Vector::new(-495.0, 0.0), 10.0), vec![1]);
+        assert_eq!(grid.query(Vector::new(-495.0, -395.0), 10.0), vec![2]);
+        assert_eq!(grid.query(Vector::new(0.0, 0.0), 10.0), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn spans_never_go_round_more_than_once() {
+        assert_eq!(span(500.0, 5000.0, 100.0, 10), 0..10);
+        assert_eq!(span(500.0, 450.0, 100.0, 10), 0..10);
+        assert_eq!(span(50.0, 60.0, 100.0, 10), -1..2);
+
+        let mut grid = SpatialHash::new(PLAYFIELD, 100.0);
+        grid.insert(1, Vector::ZERO, 5000.0);
+
+        assert_eq!(grid.query(Vector::new(-480.0, 390.0), 1.0), vec![1]);
+        assert_eq!(grid.query(Vector::ZERO, 5000.0), vec![1]);
+    }
+
+    #[test]
+    fn queries_are_sorted_without_repeats() {
+        let mut grid = SpatialHash::new(PLAYFIELD, 100.0);
+
+        for item in [9, 3, 5].iter() {
+            grid.insert(*item, Vector::new(*item as f32, 0.0), 150.0);
+        }
+
+        assert_eq!(grid.query(Vector::ZERO, 150.0), vec![3, 5, 9]);
+
+        grid.clear();
+        assert_eq!(grid.query(Vector::ZERO, 150.0), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn matches_checking_every_pair() {
+        let mut rng = GameRng::new(21);
+        let mut grid = SpatialHash::new(PLAYFIELD, 64.0);
+        let half = PLAYFIELD.divide(2.0);
+
+        let circles: Vec<(Vector, f32)> = (0..500).map(|_| {
+            (Vector::new(randf!(rng, -half.x, half.x), randf!(rng, -half.y, half.y)), randf!(rng, 1.0, 90.0))
+        }).collect();
+
+        for (i, (location, radius)) in circles.iter().enumerate() {
+            grid.insert(i, *location, *radius);
+        }
+
+        let touching = |a: usize, b: usize| {
+            let ((a_location, a_radius), (b_location, b_radius)) = (circles[a], circles[b]);
+            a != b && math::wrapped_distance(a_location, b_location, PLAYFIELD) < a_radius + b_radius
+        };
+
+        for (a, (location, radius)) in circles.iter().enumerate() {
+            let from_grid: Vec<usize> = grid.query(*location, *radius).into_iter().filter(|b| touching(a, *b)).collect();
+            let brute_force: Vec<usize> = (0..circles.len()).filter(|b| touching(a, *b)).collect();
+
+            assert_eq!(from_grid, brute_force, "circle {}", a);
+        }
+    }
+}